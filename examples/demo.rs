@@ -1,15 +1,25 @@
 use std::time::Duration;
 
 use atomic_timer::AtomicTimer;
-use egui::{vec2, CentralPanel, Color32, Slider, Visuals};
-use ehmi::{Bar, Gauge, ToggleStyle, ToggleSwitch};
+use egui::{vec2, CentralPanel, Color32, Id, Slider, Visuals};
+use ehmi::{
+    reset_peak_hold, set_touch_profile, Bar, CoilKind, Compass, CompassMode, ControlValve, Damping,
+    FailAction, Gauge, GaugeStyle, Knob, LabelPosition, LadderRung, NumberFormat, OnScreenKeyboard,
+    PeakHold, SetpointEntry, TickLabelOrientation, TimerKind, ToggleStyle, ToggleSwitch,
+    TouchProfile, Unit, ValveMode,
+};
 
 const DANGER: Color32 = Color32::RED;
 
 struct MyApp {
     value: Value,
     toggle1: bool,
+    knob: f64,
+    setpoint: f64,
+    note: String,
+    keyboard: OnScreenKeyboard,
     dark_mode: bool,
+    touch_mode: bool,
     pixels_per_point: f32,
 }
 
@@ -50,7 +60,12 @@ impl MyApp {
         Self {
             value: Value::default(),
             toggle1: true,
+            knob: 50.0,
+            setpoint: 20.0,
+            note: String::new(),
+            keyboard: OnScreenKeyboard::new(),
             dark_mode: true,
+            touch_mode: false,
             pixels_per_point: 1.0,
         }
     }
@@ -71,6 +86,15 @@ impl eframe::App for MyApp {
                     ctx.set_visuals(visuals);
                 }
 
+                if ui.checkbox(&mut self.touch_mode, "Touch").changed() {
+                    let profile = if self.touch_mode {
+                        TouchProfile::touch()
+                    } else {
+                        TouchProfile::new()
+                    };
+                    set_touch_profile(ctx, profile);
+                }
+
                 ui.separator();
 
                 ui.label("Pixels per point:");
@@ -85,6 +109,17 @@ impl eframe::App for MyApp {
                     self.pixels_per_point = 1.0;
                     ctx.set_pixels_per_point(1.0);
                 }
+
+                ui.separator();
+
+                if ui.button("Reset peaks").clicked() {
+                    reset_peak_hold(ctx, Id::new("light"));
+                }
+
+                ui.separator();
+
+                ui.label("Note:");
+                ui.text_edit_singleline(&mut self.note);
             });
         });
 
@@ -96,9 +131,11 @@ impl eframe::App for MyApp {
             ui.horizontal(|ui| {
                 ui.add(
                     Bar::new(value)
-                        .text(format!("T {:>6.1}°C", value))
+                        .text(format!("T {:>6.1}", value))
+                        .unit(Unit::Celsius)
                         .vertical(80.)
-                        .ticks(3)
+                        .ticks(6)
+                        .minor_ticks(5)
                         .range(-20.0..=80.0),
                 );
                 ui.add(
@@ -107,10 +144,29 @@ impl eframe::App for MyApp {
                         .vertical(80.)
                         .fg_color(DANGER)
                         .ticks(0)
-                        .bar_size(10.0),
+                        .bar_size(10.0)
+                        .formatter(NumberFormat::new().precision(1).suffix("°C")),
                 );
                 ui.vertical(|ui| {
-                    ui.add(Bar::new(value).text("Hello").ticks(4));
+                    ui.add(
+                        Bar::new(value)
+                            .text("Hello")
+                            .ticks(6)
+                            .minor_ticks(2)
+                            .min_max_hold(PeakHold::Decay {
+                                hold: Duration::from_secs(2),
+                                rate: 0.1,
+                            }),
+                    );
+                    ui.add(
+                        Bar::new(value - 50.0)
+                            .text("Deviation")
+                            .range(-50.0..=50.0)
+                            .origin(0.0)
+                            .length(240.0)
+                            .negative_color(DANGER)
+                            .ticks(3),
+                    );
                     ui.add(
                         Bar::new(value)
                             .text("Hello")
@@ -125,7 +181,11 @@ impl eframe::App for MyApp {
                     ui.horizontal(|ui| {
                         ui.vertical(|ui| {
                             if ui
-                                .add(ToggleSwitch::new(&mut self.toggle1).label("Hello"))
+                                .add(
+                                    ToggleSwitch::new(&mut self.toggle1)
+                                        .label("Hello")
+                                        .state_text("I", "O"),
+                                )
                                 .clicked()
                             {
                                 println!("New state: {}", self.toggle1);
@@ -135,12 +195,30 @@ impl eframe::App for MyApp {
                                     .label("Relay")
                                     .style(ToggleStyle::Relay),
                             );
+                            ui.add(
+                                ToggleSwitch::new(&mut self.toggle1)
+                                    .label("NC")
+                                    .style(ToggleStyle::RelayNc),
+                            );
                             ui.add(
                                 ToggleSwitch::new(&mut self.toggle1)
                                     .label("Valve")
-                                    .style(ToggleStyle::Valve),
+                                    .style(ToggleStyle::Valve)
+                                    .travel_time(Duration::from_secs(1)),
                             );
                         });
+                        let high = value > 50.0;
+                        ui.add(
+                            LadderRung::new()
+                                .text("Pump")
+                                .parallel([
+                                    LadderRung::new().contact("START", self.toggle1),
+                                    LadderRung::new().contact("PUMP", high),
+                                ])
+                                .nc_contact("STOP", false)
+                                .timer("T1", TimerKind::OnDelay, high, value / 100.0)
+                                .coil("PUMP", CoilKind::Normal),
+                        );
                         ui.add(
                             ToggleSwitch::new(&mut self.toggle1)
                                 .label("C")
@@ -152,6 +230,8 @@ impl eframe::App for MyApp {
                             ToggleSwitch::new(&mut self.toggle1)
                                 .label("Hello")
                                 .style(ToggleStyle::Valve)
+                                .label_position(LabelPosition::Bottom)
+                                .state_text("OPEN", "CLOSED")
                                 .size(vec2(100.0, 100.0)),
                         );
                         ui.add(
@@ -168,7 +248,14 @@ impl eframe::App for MyApp {
                     Gauge::new(value)
                         .range(0.0..=100.0)
                         .size(200.0)
-                        .text(format!("light {:>6.1}", value)),
+                        .minor_ticks(4)
+                        .style(GaugeStyle::Classic)
+                        .id(Id::new("light"))
+                        .peak_hold(PeakHold::Hold)
+                        .title("light")
+                        .value_readout(true)
+                        .formatter(NumberFormat::new().precision(1))
+                        .units_text("lx"),
                 );
                 ui.separator();
                 ui.add(
@@ -177,6 +264,8 @@ impl eframe::App for MyApp {
                         .text(format!("sphere {:>6.1}", value))
                         .size(200.0)
                         .ticks(5)
+                        .tick_label_orientation(TickLabelOrientation::Tangential)
+                        .damping(Damping::CriticallyDamped(Duration::from_millis(300)))
                         .angle_range(-90..=270),
                 );
                 ui.separator();
@@ -185,14 +274,56 @@ impl eframe::App for MyApp {
                     .size(160.0)
                     .text(format!("modern {:>6.1}", value))
                     .ticks(0)
-                    .arrow_length_factor(0.)
+                    .style(GaugeStyle::Modern)
                     .angle_range(-45..=225);
                 if !(10. ..=80.).contains(&value) {
                     gauge = gauge.fg_color(DANGER).text_color(DANGER);
                 }
                 ui.add(gauge)
             });
+            ui.horizontal(|ui| {
+                let heading = (value * 7.2) % 360.0;
+                ui.add(
+                    Compass::new(heading)
+                        .size(160.0)
+                        .wind(value * 2.0)
+                        .damping(Damping::CriticallyDamped(Duration::from_millis(300))),
+                );
+                ui.separator();
+                ui.add(
+                    Compass::new(heading)
+                        .size(160.0)
+                        .mode(CompassMode::RotatingRose)
+                        .text("heading")
+                        .damping(Damping::CriticallyDamped(Duration::from_millis(300))),
+                );
+                ui.separator();
+                ui.add(
+                    Knob::new(&mut self.knob)
+                        .size(80.0)
+                        .step(5)
+                        .default_value(50)
+                        .text("setpoint"),
+                );
+                ui.separator();
+                ui.add(
+                    SetpointEntry::new(&mut self.setpoint)
+                        .range(5..=30)
+                        .step(0.5)
+                        .unit(Unit::Celsius)
+                        .text("Room temperature"),
+                );
+                ui.separator();
+                ui.add(
+                    ControlValve::new(value)
+                        .setpoint(50)
+                        .mode(ValveMode::Auto)
+                        .fail_action(FailAction::Closed)
+                        .text("FV-101"),
+                );
+            });
         });
+        self.keyboard.show(ctx);
         if self.toggle1 {
             self.value.tick();
         }
@@ -0,0 +1,96 @@
+use std::ops::RangeInclusive;
+
+use egui::{Response, Ui, WidgetInfo, WidgetType};
+
+use crate::units::Unit;
+
+/// Alarm state of the displayed value, reported in the accessibility metadata
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlarmState {
+    /// No alarm
+    #[default]
+    Normal,
+    /// Warning (pre-alarm)
+    Warning,
+    /// Alarm
+    Alarm,
+}
+
+/// Quality of the displayed value (e.g. OPC quality), reported in the accessibility metadata
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Quality {
+    /// The value is valid
+    #[default]
+    Good,
+    /// The value may be stale or inaccurate
+    Uncertain,
+    /// The value is not valid (e.g. a sensor or communication failure)
+    Bad,
+}
+
+/// Accessibility metadata of a value component
+#[cfg_attr(not(feature = "accesskit"), allow(dead_code))]
+pub(crate) struct AccessInfo<'a> {
+    pub(crate) typ: WidgetType,
+    pub(crate) role: &'static str,
+    pub(crate) label: &'a str,
+    pub(crate) value: f64,
+    pub(crate) text: String,
+    pub(crate) range: RangeInclusive<f64>,
+    pub(crate) step: Option<f64>,
+    pub(crate) unit: Option<Unit>,
+    pub(crate) alarm: AlarmState,
+    pub(crate) quality: Quality,
+}
+
+impl AccessInfo<'_> {
+    /// Reports the metadata of the response: egui widget info (value, label, text value with
+    /// units) and, if available, AccessKit range, step, role and state descriptions
+    pub(crate) fn report(self, ui: &Ui, response: &Response) {
+        let text = match self.unit {
+            Some(unit) => format!("{} {}", self.text, unit.symbol()),
+            None => self.text.clone(),
+        };
+        response.widget_info(|| {
+            let mut info = WidgetInfo::labeled(self.typ, ui.is_enabled(), self.label);
+            info.value = Some(self.value);
+            info.current_text_value = Some(text.clone());
+            info
+        });
+        #[cfg(feature = "accesskit")]
+        ui.ctx().accesskit_node_builder(response.id, |node| {
+            node.set_numeric_value(self.value);
+            // unlimited ranges (e.g. of setpoint entries) have no bounds
+            if self.range.start().is_finite() {
+                node.set_min_numeric_value(*self.range.start());
+            }
+            if self.range.end().is_finite() {
+                node.set_max_numeric_value(*self.range.end());
+            }
+            if let Some(step) = self.step {
+                node.set_numeric_value_step(step);
+            }
+            node.set_value(text.clone());
+            node.set_role_description(self.role);
+            if let Some(state) = self.state_description() {
+                node.set_state_description(state);
+            }
+        });
+    }
+
+    #[cfg_attr(not(feature = "accesskit"), allow(dead_code))]
+    fn state_description(&self) -> Option<String> {
+        let alarm = match self.alarm {
+            AlarmState::Normal => None,
+            AlarmState::Warning => Some("warning"),
+            AlarmState::Alarm => Some("alarm"),
+        };
+        let quality = match self.quality {
+            Quality::Good => None,
+            Quality::Uncertain => Some("quality uncertain"),
+            Quality::Bad => Some("quality bad"),
+        };
+        let parts: Vec<&str> = alarm.into_iter().chain(quality).collect();
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use egui::{Id, Ui};
+
+/// Value animation mode. The displayed value (fill, needle) eases toward the actual one, while
+/// the text can still show the exact value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Damping {
+    /// First-order lag with the given time constant
+    FirstOrderLag(Duration),
+    /// Critically damped (no overshoot) second-order response with the given time constant
+    CriticallyDamped(Duration),
+}
+
+/// Max time step, to avoid jumps after the component has not been painted for a while
+const MAX_STEP: f64 = 0.1;
+
+/// Animated value state, stored in egui memory
+#[derive(Clone, Copy, Debug)]
+struct AnimationState {
+    value: f64,
+    velocity: f64,
+    updated: f64,
+}
+
+/// Returns the displayed value, eased toward the target. `span` is the value range size, used to
+/// detect the end of the animation
+pub(crate) fn animate_value(ui: &Ui, id: Id, damping: Damping, target: f64, span: f64) -> f64 {
+    let now = ui.input(|i| i.time);
+    let Some(mut state) = ui.data(|d| d.get_temp::<AnimationState>(id)) else {
+        ui.data_mut(|d| {
+            d.insert_temp(
+                id,
+                AnimationState {
+                    value: target,
+                    velocity: 0.0,
+                    updated: now,
+                },
+            );
+        });
+        return target;
+    };
+    let dt = (now - state.updated).clamp(0.0, MAX_STEP);
+    let offset = state.value - target;
+    match damping {
+        Damping::FirstOrderLag(time_constant) => {
+            let tau = time_constant.as_secs_f64();
+            let k = if tau > 0.0 { (-dt / tau).exp() } else { 0.0 };
+            state.value = target + offset * k;
+            state.velocity = 0.0;
+        }
+        Damping::CriticallyDamped(time_constant) => {
+            let tau = time_constant.as_secs_f64();
+            if tau > 0.0 {
+                let omega = 1.0 / tau;
+                let k = (-omega * dt).exp();
+                let temp = (state.velocity + omega * offset) * dt;
+                state.value = target + (offset + temp) * k;
+                state.velocity = (state.velocity - omega * temp) * k;
+            } else {
+                state.value = target;
+                state.velocity = 0.0;
+            }
+        }
+    }
+    state.updated = now;
+    let eps = span.abs() * 1e-4;
+    if (state.value - target).abs() <= eps && state.velocity.abs() <= eps {
+        state.value = target;
+        state.velocity = 0.0;
+    } else {
+        ui.ctx().request_repaint();
+    }
+    ui.data_mut(|d| d.insert_temp(id, state));
+    state.value
+}
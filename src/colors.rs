@@ -2,6 +2,7 @@ use egui::Color32;
 
 pub const SUCCESS: Color32 = Color32::GREEN;
 pub const WARN: Color32 = Color32::ORANGE;
+pub const FAULT: Color32 = Color32::RED;
 
 pub const GRAY_DARK: Color32 = Color32::from_gray(47);
 pub const GRAY: Color32 = Color32::from_gray(169);
@@ -1,48 +1,104 @@
 use core::fmt;
 use std::ops::RangeInclusive;
 
-use egui::{pos2, vec2, Align2, Color32, FontId, Rect, Response, RichText, Stroke, Ui};
+use egui::{
+    pos2, vec2, Align2, Color32, FontId, Id, Painter, Pos2, Rect, Response, RichText, Sense,
+    Stroke, StrokeKind, Ui,
+};
 
+use crate::accessibility::{AccessInfo, AlarmState, Quality};
+use crate::animation::{animate_value, Damping};
 use crate::colors::{get_text_color, GRAY, GRAY_DARK, SUCCESS};
+use crate::format::{NumberFormat, ValueFormatter};
+use crate::numeric::Numeric;
+use crate::peak::{PeakHold, PeakHoldConfig, PeakState};
+use crate::scale::Scale;
+use crate::ticks::Ticks;
+use crate::units::{Unit, UnitConversion, UnitSystem};
+
+const TICK_LABEL_MARGIN: f32 = 4.0;
+const DEFAULT_HORIZONTAL_LENGTH: f32 = 180.0;
+const DEFAULT_VERTICAL_LENGTH: f32 = 240.0;
+const MIN_LENGTH: f32 = 20.0;
+
+#[derive(Clone, Copy, Debug)]
+enum BarLength {
+    Default,
+    Fixed(f32),
+    Fill,
+}
 
 /// Horizontal or vertical bar component
 pub struct Bar {
     text: String,
-    value: f32,
+    value: f64,
+    integral: bool,
     font_size: f32,
     label_size: f32,
     bar_size: f32,
     fg_color: Color32,
-    min: f32,
-    max: f32,
+    negative_color: Option<Color32>,
+    origin: Option<f64>,
+    min: f64,
+    max: f64,
     vertical: Option<f32>,
+    length: BarLength,
     ticks: usize,
+    minor_ticks: usize,
+    tick_labels: bool,
+    formatter: Option<Box<dyn ValueFormatter>>,
+    scale: Scale,
+    unit: Option<Unit>,
+    unit_system: Option<UnitSystem>,
+    id: Option<Id>,
+    peak_hold: Option<PeakHoldConfig>,
+    damping: Option<Damping>,
+    alarm: AlarmState,
+    quality: Quality,
 }
 
 impl Bar {
     /// Create a new bar
     pub fn new<V>(value: V) -> Self
     where
-        V: Into<f32>,
+        V: Numeric,
     {
         Self {
             text: <_>::default(),
-            value: value.into(),
+            value: value.to_f64(),
+            integral: V::INTEGRAL,
             font_size: 16.0,
             label_size: 10.0,
             bar_size: 5.0,
             fg_color: SUCCESS,
+            negative_color: None,
+            origin: None,
             min: 0.0,
             max: 100.0,
             vertical: None,
+            length: BarLength::Default,
             ticks: 0,
+            minor_ticks: 0,
+            tick_labels: true,
+            formatter: None,
+            scale: Scale::Linear,
+            unit: None,
+            unit_system: None,
+            id: None,
+            peak_hold: None,
+            damping: None,
+            alarm: AlarmState::Normal,
+            quality: Quality::Good,
         }
     }
 
     /// Set the range of the bar
-    pub fn range(mut self, range: RangeInclusive<f32>) -> Self {
-        self.min = *range.start();
-        self.max = *range.end();
+    pub fn range<V>(mut self, range: RangeInclusive<V>) -> Self
+    where
+        V: Numeric,
+    {
+        self.min = range.start().to_f64();
+        self.max = range.end().to_f64();
         self
     }
 
@@ -52,6 +108,20 @@ impl Bar {
         self
     }
 
+    /// Set the bar length (width for horizontal, height for vertical bars), the default is 180
+    /// points for horizontal and 240 points for vertical bars
+    pub fn length(mut self, length: f32) -> Self {
+        self.length = BarLength::Fixed(length.max(MIN_LENGTH));
+        self
+    }
+
+    /// Make the bar fill the available width (horizontal) or height (vertical bars). The default
+    /// length is used if the available size is not limited (e.g. in scroll areas)
+    pub fn fill_available(mut self) -> Self {
+        self.length = BarLength::Fill;
+        self
+    }
+
     /// Set the bar text
     pub fn text(mut self, text: impl fmt::Display) -> Self {
         self.text = text.to_string();
@@ -82,44 +152,428 @@ impl Bar {
         self
     }
 
-    /// Set the number of the ticks, number below 2 disables the ticks
+    /// Sets the bar foreground color for values below the origin (the foreground color is used
+    /// by default)
+    pub fn negative_color(mut self, color: Color32) -> Self {
+        self.negative_color = Some(color);
+        self
+    }
+
+    /// Fill the bar from the origin value (e.g. 0 for -100..=100 ranges) instead of the range
+    /// start
+    pub fn origin<V>(mut self, origin: V) -> Self
+    where
+        V: Numeric,
+    {
+        self.origin = Some(origin.to_f64());
+        self
+    }
+
+    /// Set the maximum number of the major ticks, number below 2 disables the ticks. The ticks
+    /// are placed at "nice" values (1-2-5 steps)
     pub fn ticks(mut self, n: usize) -> Self {
         self.ticks = n;
         self
     }
 
-    fn vertical_ui(self, ui: &mut Ui, vertical_size: f32, value: f32) -> Response {
-        const HEIGHT: f32 = 240.0;
+    /// Set the number of minor tick intervals between the major ticks, number below 2 disables
+    /// the minor ticks
+    pub fn minor_ticks(mut self, n: usize) -> Self {
+        self.minor_ticks = n;
+        self
+    }
+
+    /// Show the value labels of the major ticks (enabled by default)
+    pub fn tick_labels(mut self, show: bool) -> Self {
+        self.tick_labels = show;
+        self
+    }
+
+    /// Set the formatter for the numeric labels
+    pub fn formatter(mut self, formatter: impl ValueFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Set the value scale (linear by default)
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the engineering unit of the value and the range, the unit symbol is displayed next to
+    /// the text
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Display the value and the range in the target unit system (requires the unit to be set)
+    pub fn unit_system(mut self, system: UnitSystem) -> Self {
+        self.unit_system = Some(system);
+        self
+    }
+
+    /// Set the bar id, required to keep the peak-hold markers of bars with changing layout
+    /// positions and to reset them with [`reset_peak_hold`](crate::reset_peak_hold)
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Show the peak (max) marker. The marker is reset by double click
+    pub fn peak_hold(mut self, mode: PeakHold) -> Self {
+        self.peak_hold = Some(PeakHoldConfig { mode, min: false });
+        self
+    }
+
+    /// Show the min and max markers. The markers are reset by double click
+    pub fn min_max_hold(mut self, mode: PeakHold) -> Self {
+        self.peak_hold = Some(PeakHoldConfig { mode, min: true });
+        self
+    }
+
+    /// Animate the fill, easing it toward the new values
+    pub fn damping(mut self, damping: Damping) -> Self {
+        self.damping = Some(damping);
+        self
+    }
+
+    /// Set the alarm state, reported in the accessibility metadata
+    pub fn alarm(mut self, alarm: AlarmState) -> Self {
+        self.alarm = alarm;
+        self
+    }
+
+    /// Set the value quality, reported in the accessibility metadata
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    fn report_access(&self, ui: &Ui, response: &Response, value: f64) {
+        AccessInfo {
+            typ: egui::WidgetType::ProgressIndicator,
+            role: "bar",
+            label: &self.text,
+            value,
+            text: self.format_value(value),
+            range: self.min..=self.max,
+            step: None,
+            unit: UnitConversion::new(self.unit, self.unit_system).map(UnitConversion::unit),
+            alarm: self.alarm,
+            quality: self.quality,
+        }
+        .report(ui, response);
+    }
+
+    /// The displayed (animated if set) value
+    fn display_value(&self, ui: &Ui, response: &Response, value: f64) -> f64 {
+        if let Some(damping) = self.damping {
+            animate_value(
+                ui,
+                self.id.unwrap_or(response.id),
+                damping,
+                value,
+                self.max - self.min,
+            )
+        } else {
+            value
+        }
+    }
+
+    fn sense(&self) -> Sense {
+        if self.peak_hold.is_some() {
+            Sense::click()
+        } else {
+            Sense::hover()
+        }
+    }
+
+    fn update_peaks(&self, ui: &Ui, response: &Response, value: f64) -> Option<PeakState> {
+        self.peak_hold.map(|config| {
+            PeakState::update(
+                ui,
+                response,
+                self.id.unwrap_or(response.id),
+                config,
+                value,
+                self.max - self.min,
+            )
+        })
+    }
+
+    fn apply_units(&mut self) {
+        let Some(conversion) = UnitConversion::new(self.unit, self.unit_system) else {
+            return;
+        };
+        // converted values are not integral anymore
+        self.integral &= Some(conversion.unit()) == self.unit;
+        self.value = conversion.apply(self.value);
+        self.min = conversion.apply(self.min);
+        self.max = conversion.apply(self.max);
+        self.origin = self.origin.map(|v| conversion.apply(v));
+        self.scale.convert_values(|v| conversion.apply(v));
+        let symbol = conversion.unit().symbol();
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        self.text.push_str(symbol);
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        if let Some(ref formatter) = self.formatter {
+            formatter.format(value)
+        } else if self.integral && value.fract() == 0.0 {
+            NumberFormat::new().precision(0).format(value)
+        } else {
+            NumberFormat::new().format(value)
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn ratio(&self, value: f64) -> f32 {
+        self.scale.normalize(value, self.min, self.max) as f32
+    }
+
+    fn scale_ticks(&self) -> Ticks {
+        Ticks::new(
+            &self.scale,
+            self.min,
+            self.max,
+            self.ticks,
+            self.minor_ticks,
+            self.integral,
+        )
+    }
+
+    /// Labels of the major ticks, except the ones at the range ends which are always labeled
+    fn labeled_ticks(&self, ticks: &Ticks) -> Vec<(f64, String)> {
+        if !self.tick_labels {
+            return Vec::new();
+        }
+        let eps = (self.max - self.min).abs() * 1e-9;
+        ticks
+            .major
+            .iter()
+            .filter(|v| (*v - self.min).abs() > eps && (*v - self.max).abs() > eps)
+            .map(|v| (*v, self.format_value(*v)))
+            .collect()
+    }
+
+    fn max_label_width(&self, ui: &Ui, labels: &[(f64, String)]) -> f32 {
+        ui.fonts(|f| {
+            labels
+                .iter()
+                .map(|(_, label)| {
+                    f.layout_no_wrap(
+                        label.clone(),
+                        FontId::proportional(self.label_size),
+                        Color32::PLACEHOLDER,
+                    )
+                    .size()
+                    .x
+                })
+                .fold(0.0, f32::max)
+        })
+    }
+
+    /// Position of the value along the bar axis
+    fn position(&self, bar_rect: Rect, vertical: bool, value: f64) -> f32 {
+        if vertical {
+            bar_rect.max.y - bar_rect.height() * self.ratio(value)
+        } else {
+            bar_rect.min.x + bar_rect.width() * self.ratio(value)
+        }
+    }
+
+    /// A line across the bar at the value position
+    fn cross_line(
+        &self,
+        bar_rect: Rect,
+        vertical: bool,
+        value: f64,
+        half_length: f32,
+    ) -> [Pos2; 2] {
+        let p = self.position(bar_rect, vertical, value);
+        let c = bar_rect.center();
+        if vertical {
+            [pos2(c.x - half_length, p), pos2(c.x + half_length, p)]
+        } else {
+            [pos2(p, c.y - half_length), pos2(p, c.y + half_length)]
+        }
+    }
+
+    fn paint_peaks(
+        &self,
+        painter: &Painter,
+        bar_rect: Rect,
+        vertical: bool,
+        peaks: Option<PeakState>,
+        color: Color32,
+    ) {
+        let (Some(peaks), Some(config)) = (peaks, self.peak_hold) else {
+            return;
+        };
+        let half_length = if vertical {
+            bar_rect.width()
+        } else {
+            bar_rect.height()
+        } / 2.0
+            + 3.0;
+        let stroke = Stroke::new(1.5, color);
+        painter.line_segment(
+            self.cross_line(bar_rect, vertical, peaks.max, half_length),
+            stroke,
+        );
+        if config.min {
+            painter.line_segment(
+                self.cross_line(bar_rect, vertical, peaks.min, half_length),
+                stroke,
+            );
+        }
+    }
+
+    fn paint_ticks(
+        &self,
+        painter: &Painter,
+        bar_rect: Rect,
+        vertical: bool,
+        ticks: &Ticks,
+        labels: Vec<(f64, String)>,
+        color: Color32,
+    ) {
+        let line = |v: f64, half_length: f32, stroke: Stroke| {
+            painter.line_segment(self.cross_line(bar_rect, vertical, v, half_length), stroke);
+        };
+        let thickness = if vertical {
+            bar_rect.width()
+        } else {
+            bar_rect.height()
+        };
+        for v in &ticks.minor {
+            line(*v, thickness / 2.0, Stroke::new(0.5, color));
+        }
+        for v in &ticks.major {
+            line(*v, thickness / 2.0 + 2.0, Stroke::new(1.0, color));
+        }
+        let mut last_label_rect: Option<Rect> = None;
+        for (v, label) in labels {
+            let p = self.position(bar_rect, vertical, v);
+            let (pos, anchor) = if vertical {
+                (
+                    pos2(bar_rect.max.x + TICK_LABEL_MARGIN, p),
+                    Align2::LEFT_CENTER,
+                )
+            } else {
+                (
+                    pos2(p, bar_rect.max.y + TICK_LABEL_MARGIN),
+                    Align2::CENTER_TOP,
+                )
+            };
+            let galley =
+                painter.layout_no_wrap(label, FontId::proportional(self.label_size), color);
+            let label_rect = anchor.anchor_size(pos, galley.size());
+            if last_label_rect.is_some_and(|r| r.expand(1.0).intersects(label_rect)) {
+                continue;
+            }
+            painter.galley(label_rect.min, galley, color);
+            last_label_rect = Some(label_rect);
+        }
+    }
+
+    fn paint_bar(
+        &self,
+        painter: &Painter,
+        bar_rect: Rect,
+        vertical: bool,
+        value: f64,
+        bg_color: Color32,
+        origin_color: Color32,
+    ) {
+        let (bg_radius, fill_radius) = if vertical {
+            (3.0, 2.0)
+        } else {
+            let radius = bar_rect.height() / 2.0;
+            (radius, radius)
+        };
+        painter.rect(
+            bar_rect,
+            bg_radius,
+            bg_color,
+            Stroke::NONE,
+            StrokeKind::Inside,
+        );
+        let origin = self.origin.unwrap_or(self.min);
+        let origin_pos = self.ratio(origin);
+        let value_pos = self.ratio(value);
+        let (from, to) = (origin_pos.min(value_pos), origin_pos.max(value_pos));
+        let fill_rect = if vertical {
+            Rect::from_x_y_ranges(
+                bar_rect.x_range(),
+                (bar_rect.max.y - bar_rect.height() * to)
+                    ..=(bar_rect.max.y - bar_rect.height() * from),
+            )
+        } else {
+            Rect::from_x_y_ranges(
+                (bar_rect.min.x + bar_rect.width() * from)
+                    ..=(bar_rect.min.x + bar_rect.width() * to),
+                bar_rect.y_range(),
+            )
+        };
+        let color = if value < origin {
+            self.negative_color.unwrap_or(self.fg_color)
+        } else {
+            self.fg_color
+        };
+        painter.rect_filled(fill_rect, fill_radius, color);
+        if self.origin.is_some() {
+            let points = if vertical {
+                let y = bar_rect.max.y - bar_rect.height() * origin_pos;
+                [pos2(bar_rect.min.x, y), pos2(bar_rect.max.x, y)]
+            } else {
+                let x = bar_rect.min.x + bar_rect.width() * origin_pos;
+                [pos2(x, bar_rect.min.y), pos2(x, bar_rect.max.y)]
+            };
+            painter.line_segment(points, Stroke::new(1.0, origin_color));
+        }
+    }
+
+    fn vertical_ui(self, ui: &mut Ui, vertical_size: f32, value: f64) -> Response {
         const VALUE_OFFSET: f32 = 16.0;
         const LABEL_MARGIN: f32 = 4.0;
 
-        let total_width = self.bar_size + VALUE_OFFSET + vertical_size;
-        let total_height = HEIGHT + (LABEL_MARGIN + self.label_size) * 2.0;
+        let height = match self.length {
+            BarLength::Fixed(length) => length,
+            // the available size is infinite in scroll areas
+            BarLength::Fill if ui.available_height().is_finite() => {
+                (ui.available_height() - (LABEL_MARGIN + self.label_size) * 2.0).max(MIN_LENGTH)
+            }
+            BarLength::Default | BarLength::Fill => DEFAULT_VERTICAL_LENGTH,
+        };
+
+        let ticks = self.scale_ticks();
+        let labels = self.labeled_ticks(&ticks);
+        let labels_width = if labels.is_empty() {
+            0.0
+        } else {
+            self.max_label_width(ui, &labels) + TICK_LABEL_MARGIN
+        };
+
+        let total_width = self.bar_size + labels_width + VALUE_OFFSET + vertical_size;
+        let total_height = height + (LABEL_MARGIN + self.label_size) * 2.0;
 
         let (rect, response) =
-            ui.allocate_exact_size(vec2(total_width, total_height), egui::Sense::hover());
+            ui.allocate_exact_size(vec2(total_width, total_height), self.sense());
+        self.report_access(ui, &response, value);
+        let peaks = self.update_peaks(ui, &response, value);
+        let value = self.display_value(ui, &response, value);
 
         if ui.is_rect_visible(rect) {
             let painter = ui.painter();
             let bar_top = rect.min.y + self.label_size + LABEL_MARGIN;
             let bar_rect =
-                Rect::from_min_size(pos2(rect.min.x, bar_top), vec2(self.bar_size, HEIGHT));
-
-            painter.rect(
-                bar_rect,
-                3.0,
-                ui.style().visuals.clone().extreme_bg_color,
-                Stroke::NONE,
-                egui::StrokeKind::Inside,
-            );
-
-            let fill_height = HEIGHT * (value - self.min) / (self.max - self.min);
-            let fill_rect = Rect::from_min_size(
-                pos2(bar_rect.min.x, bar_rect.max.y - fill_height),
-                vec2(self.bar_size, fill_height),
-            );
-
-            painter.rect_filled(fill_rect, 2.0, self.fg_color);
+                Rect::from_min_size(pos2(rect.min.x, bar_top), vec2(self.bar_size, height));
 
             let cx = bar_rect.center().x;
 
@@ -129,10 +583,19 @@ impl Bar {
                 GRAY_DARK
             };
 
+            self.paint_bar(
+                painter,
+                bar_rect,
+                true,
+                value,
+                ui.visuals().extreme_bg_color,
+                label_color,
+            );
+
             painter.text(
                 pos2(cx, bar_rect.min.y - LABEL_MARGIN),
                 Align2::CENTER_BOTTOM,
-                self.max,
+                self.format_value(self.max),
                 FontId::proportional(self.label_size),
                 label_color,
             );
@@ -140,7 +603,7 @@ impl Bar {
             painter.text(
                 pos2(cx, bar_rect.max.y + LABEL_MARGIN),
                 Align2::CENTER_TOP,
-                self.min,
+                self.format_value(self.min),
                 FontId::proportional(self.label_size),
                 label_color,
             );
@@ -148,24 +611,17 @@ impl Bar {
             let text_color = get_text_color(ui);
 
             painter.text(
-                pos2(bar_rect.max.x + VALUE_OFFSET, bar_rect.center().y),
+                pos2(
+                    bar_rect.max.x + labels_width + VALUE_OFFSET,
+                    bar_rect.center().y,
+                ),
                 Align2::LEFT_CENTER,
-                self.text,
+                &self.text,
                 FontId::proportional(self.font_size),
                 text_color,
             );
-            if self.ticks > 1 {
-                let diff = bar_rect.width() / 2.0 + 2.0;
-                let cx = bar_rect.center().x;
-                for i in 1..self.ticks + 1 {
-                    let t = i as f32 / (self.ticks + 1) as f32;
-                    let y = bar_rect.min.y + HEIGHT * t;
-                    painter.line_segment(
-                        [pos2(cx - diff, y), pos2(cx + diff, y)],
-                        Stroke::new(1.0, label_color),
-                    );
-                }
-            }
+            self.paint_ticks(painter, bar_rect, true, &ticks, labels, label_color);
+            self.paint_peaks(painter, bar_rect, true, peaks, text_color);
         }
 
         response
@@ -173,12 +629,13 @@ impl Bar {
 }
 
 impl egui::Widget for Bar {
-    fn ui(self, ui: &mut Ui) -> Response {
+    #[allow(clippy::too_many_lines)]
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.apply_units();
         let value = self.value.clamp(self.min, self.max);
         if let Some(vertical_size) = self.vertical {
             return self.vertical_ui(ui, vertical_size, value);
         }
-        let bar_width = 180.0;
         let label_color = if ui.visuals().dark_mode {
             GRAY
         } else {
@@ -186,8 +643,8 @@ impl egui::Widget for Bar {
         };
         let text_color = get_text_color(ui);
         let painter = ui.painter();
-        let min_str = format!("{}", self.min);
-        let max_str = format!("{}", self.max);
+        let min_str = self.format_value(self.min);
+        let max_str = self.format_value(self.max);
 
         let min_text = RichText::new(&min_str)
             .color(label_color)
@@ -214,21 +671,38 @@ impl egui::Widget for Bar {
 
         let min_label_width = gallery_min_label.size().x;
         let max_label_width = gallery_max_label.size().x;
-        let total_width = min_label_width + bar_width + max_label_width;
         let line_height = self.bar_size.max(self.label_size);
         let label_offset = (line_height - self.label_size) / 2.0;
+        let labels_width =
+            min_label_width + max_label_width + (label_offset + ui.spacing().item_spacing.x) * 2.0;
+        let bar_width = match self.length {
+            BarLength::Fixed(length) => length,
+            BarLength::Fill if ui.available_width().is_finite() => {
+                (ui.available_width() - labels_width).max(MIN_LENGTH)
+            }
+            BarLength::Default | BarLength::Fill => DEFAULT_HORIZONTAL_LENGTH,
+        };
+        let total_width = labels_width + bar_width;
         let text_offset = (total_width - text_width).max(0.0) / 2.0;
 
-        let desired_size = vec2(total_width, line_height * 2.0 + self.font_size);
-        let (rect, response) = ui.allocate_exact_size(desired_size, egui::Sense::hover());
+        let ticks = self.scale_ticks();
+        let labels = self.labeled_ticks(&ticks);
+        let labels_height = if labels.is_empty() {
+            0.0
+        } else {
+            self.label_size + TICK_LABEL_MARGIN
+        };
+
+        let desired_size = vec2(
+            total_width,
+            line_height * 2.0 + labels_height + self.font_size,
+        );
+        let (rect, response) = ui.allocate_exact_size(desired_size, self.sense());
+        self.report_access(ui, &response, value);
+        let peaks = self.update_peaks(ui, &response, value);
+        let value = self.display_value(ui, &response, value);
         if ui.is_rect_visible(rect) {
-            let range = self.max - self.min;
-            let v = (value - self.min) / range;
-            let progress_bar = egui::ProgressBar::new(v)
-                .fill(self.fg_color)
-                .desired_height(self.bar_size)
-                .desired_width(bar_width);
-            let mut progress_bar_response = None;
+            let mut bar_rect = None;
             ui.allocate_new_ui(
                 egui::UiBuilder::new().max_rect(rect).layout(*ui.layout()),
                 |ui| {
@@ -236,11 +710,19 @@ impl egui::Widget for Bar {
                         ui.horizontal(|ui| {
                             ui.add_space(label_offset);
                             ui.label(min_text);
-                            progress_bar_response = Some(ui.add(progress_bar));
+                            bar_rect = Some(
+                                ui.allocate_exact_size(
+                                    vec2(bar_width, self.bar_size),
+                                    egui::Sense::hover(),
+                                )
+                                .0,
+                            );
                             ui.add_space(label_offset);
                             ui.label(max_text);
                         });
 
+                        ui.add_space(labels_height);
+
                         ui.horizontal(|ui| {
                             ui.add_space(text_offset);
                             ui.label(text);
@@ -248,20 +730,17 @@ impl egui::Widget for Bar {
                     });
                 },
             );
-            if self.ticks > 1 {
-                if let Some(pb_response) = progress_bar_response {
-                    let bar_rect = pb_response.rect;
-                    let diff = bar_rect.height() / 2.0 + 2.0;
-                    let cy = bar_rect.center().y;
-                    for i in 1..self.ticks + 1 {
-                        let t = i as f32 / (self.ticks + 1) as f32;
-                        let x = bar_rect.left() + bar_rect.width() * t;
-                        ui.painter().line_segment(
-                            [pos2(x, cy - diff), pos2(x, cy + diff)],
-                            Stroke::new(1.0, label_color),
-                        );
-                    }
-                }
+            if let Some(bar_rect) = bar_rect {
+                self.paint_bar(
+                    ui.painter(),
+                    bar_rect,
+                    false,
+                    value,
+                    ui.visuals().extreme_bg_color,
+                    label_color,
+                );
+                self.paint_ticks(ui.painter(), bar_rect, false, &ticks, labels, label_color);
+                self.paint_peaks(ui.painter(), bar_rect, false, peaks, text_color);
             }
         }
         response
@@ -0,0 +1,364 @@
+use egui::{Align2, Color32, FontId, Id, Rect, Response, Sense, Shape, Stroke, Ui, Vec2};
+
+use super::gauge::position_from_angle;
+use crate::accessibility::{AccessInfo, AlarmState, Quality};
+use crate::animation::{animate_value, Damping};
+use crate::colors::{get_text_color, GRAY, SUCCESS};
+use crate::numeric::Numeric;
+use core::fmt;
+
+/// What rotates on the compass dial
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompassMode {
+    /// The rose is fixed (north up), the needle points to the heading
+    #[default]
+    RotatingNeedle,
+    /// The rose rotates, the heading is read at the fixed lubber mark on top
+    RotatingRose,
+}
+
+const CARDINALS: [(i32, &str); 4] = [(0, "N"), (90, "E"), (180, "S"), (270, "W")];
+
+/// Compass (heading) dial, with an optional wind direction pointer. Headings are in degrees,
+/// clockwise from north
+pub struct Compass {
+    heading: f64,
+    wind: Option<f64>,
+    size: f32,
+    mode: CompassMode,
+    degree_labels: bool,
+    readout: bool,
+    text: Option<String>,
+    color: Color32,
+    wind_color: Color32,
+    text_color: Option<Color32>,
+    id: Option<Id>,
+    damping: Option<Damping>,
+    alarm: AlarmState,
+    quality: Quality,
+}
+
+impl Compass {
+    /// Create a new compass with the heading in degrees
+    pub fn new<V>(heading: V) -> Self
+    where
+        V: Numeric,
+    {
+        Self {
+            heading: heading.to_f64(),
+            wind: None,
+            size: 200.0,
+            mode: CompassMode::RotatingNeedle,
+            degree_labels: true,
+            readout: true,
+            text: None,
+            color: SUCCESS,
+            wind_color: Color32::LIGHT_BLUE,
+            text_color: None,
+            id: None,
+            damping: None,
+            alarm: AlarmState::Normal,
+            quality: Quality::Good,
+        }
+    }
+
+    /// Set the size of the compass
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the dial mode (rotating needle by default)
+    pub fn mode(mut self, mode: CompassMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Show the wind pointer, the direction is where the wind blows from, in degrees
+    pub fn wind<V>(mut self, direction: V) -> Self
+    where
+        V: Numeric,
+    {
+        self.wind = Some(direction.to_f64());
+        self
+    }
+
+    /// Show the degree labels every 30 degrees (enabled by default)
+    pub fn degree_labels(mut self, degree_labels: bool) -> Self {
+        self.degree_labels = degree_labels;
+        self
+    }
+
+    /// Show the heading readout (enabled by default)
+    pub fn readout(mut self, readout: bool) -> Self {
+        self.readout = readout;
+        self
+    }
+
+    /// Set the inner text of the compass
+    pub fn text(mut self, text: impl fmt::Display) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Set the color of the needle (the lubber mark in the rotating rose mode)
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the color of the wind pointer
+    pub fn wind_color(mut self, color: Color32) -> Self {
+        self.wind_color = color;
+        self
+    }
+
+    /// Set the color of the text
+    pub fn text_color(mut self, color: Color32) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Set the compass id, required to keep the animation state of compasses with changing
+    /// layout positions
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Animate the needle (the rose), easing it toward the new heading. Transitions across north
+    /// take the shortest way (e.g. 359 to 1 degrees turns by 2 degrees, not by 358)
+    pub fn damping(mut self, damping: Damping) -> Self {
+        self.damping = Some(damping);
+        self
+    }
+
+    /// Set the alarm state, reported in the accessibility metadata
+    pub fn alarm(mut self, alarm: AlarmState) -> Self {
+        self.alarm = alarm;
+        self
+    }
+
+    /// Set the value quality, reported in the accessibility metadata
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.size / 2.0 - 2.0
+    }
+
+    /// Heading rounded to whole degrees, 359.6 is displayed as 000
+    fn heading_text(heading: f64) -> String {
+        format!("{:03.0}°", heading.rem_euclid(360.0).round() % 360.0)
+    }
+
+    /// Screen angle (counterclockwise from the east, as in the gauge) of the dial bearing
+    #[allow(clippy::cast_possible_truncation)]
+    fn bearing_angle(bearing: f64, rose_rotation: f64) -> f32 {
+        (90.0 - (bearing - rose_rotation)) as f32
+    }
+
+    /// Eases the heading toward the target, unwrapping it so the animation never sweeps across
+    /// the whole dial
+    fn animate(ui: &Ui, id: Id, damping: Option<Damping>, target: f64) -> f64 {
+        let Some(damping) = damping else {
+            return target;
+        };
+        let unwrap_id = id.with("unwrapped");
+        let unwrapped = if let Some(previous) = ui.data(|d| d.get_temp::<f64>(unwrap_id)) {
+            let mut delta = (target - previous).rem_euclid(360.0);
+            if delta > 180.0 {
+                delta -= 360.0;
+            }
+            previous + delta
+        } else {
+            target
+        };
+        ui.data_mut(|d| d.insert_temp(unwrap_id, unwrapped));
+        animate_value(ui, id.with("animated"), damping, unwrapped, 360.0)
+    }
+
+    fn paint(&self, ui: &mut Ui, rect: Rect, heading: f64, wind: Option<f64>) {
+        let center = rect.center();
+        let radius = self.radius();
+        let text_color = self.text_color.unwrap_or_else(|| get_text_color(ui));
+        let tick_color = self.text_color.unwrap_or(if ui.visuals().dark_mode {
+            GRAY
+        } else {
+            Color32::GRAY
+        });
+        let rose_rotation = match self.mode {
+            CompassMode::RotatingNeedle => 0.0,
+            CompassMode::RotatingRose => heading,
+        };
+
+        ui.painter().circle(
+            center,
+            radius,
+            ui.visuals().extreme_bg_color,
+            ui.visuals().widgets.noninteractive.bg_stroke,
+        );
+
+        for bearing in (0..360).step_by(5) {
+            let angle = Self::bearing_angle(f64::from(bearing), rose_rotation);
+            let (length, width) = if bearing % 30 == 0 {
+                (radius / 10.0, 1.5)
+            } else if bearing % 10 == 0 {
+                (radius / 16.0, 1.0)
+            } else {
+                (radius / 28.0, 0.5)
+            };
+            ui.painter().line_segment(
+                [
+                    position_from_angle(rect, angle, radius - length),
+                    position_from_angle(rect, angle, radius),
+                ],
+                Stroke::new(width, tick_color),
+            );
+        }
+
+        let label_radius = radius * 0.75;
+        for (bearing, name) in CARDINALS {
+            let angle = Self::bearing_angle(f64::from(bearing), rose_rotation);
+            ui.painter().text(
+                position_from_angle(rect, angle, label_radius),
+                Align2::CENTER_CENTER,
+                name,
+                FontId::proportional(self.size / 10.0),
+                text_color,
+            );
+        }
+        if self.degree_labels {
+            for bearing in (30..360).step_by(30).filter(|b| b % 90 != 0) {
+                let angle = Self::bearing_angle(f64::from(bearing), rose_rotation);
+                ui.painter().text(
+                    position_from_angle(rect, angle, label_radius),
+                    Align2::CENTER_CENTER,
+                    bearing.to_string(),
+                    FontId::proportional(self.size / 18.0),
+                    tick_color,
+                );
+            }
+        }
+
+        if let Some(wind) = wind {
+            self.paint_wind(ui, rect, Self::bearing_angle(wind, rose_rotation));
+        }
+
+        match self.mode {
+            CompassMode::RotatingNeedle => {
+                self.paint_needle(ui, rect, Self::bearing_angle(heading, 0.0));
+            }
+            CompassMode::RotatingRose => self.paint_lubber_mark(ui, rect),
+        }
+
+        let mut text_pos = center + Vec2::new(0.0, radius * 0.35);
+        if self.readout {
+            ui.painter().text(
+                text_pos,
+                Align2::CENTER_CENTER,
+                Self::heading_text(self.heading),
+                FontId::proportional(self.size / 9.0),
+                text_color,
+            );
+            text_pos.y += self.size / 9.0;
+        }
+        if let Some(ref text) = self.text {
+            ui.painter().text(
+                text_pos,
+                Align2::CENTER_CENTER,
+                text,
+                FontId::proportional(self.size / 14.0),
+                text_color,
+            );
+        }
+    }
+
+    fn paint_needle(&self, ui: &mut Ui, rect: Rect, angle: f32) {
+        let center = rect.center();
+        let tip = position_from_angle(rect, angle, self.radius() * 0.85);
+        let tail = position_from_angle(rect, angle + 180.0, self.radius() * 0.6);
+        let normal = (tip - center).normalized().rot90() * (self.size / 40.0).max(2.0);
+        ui.painter().add(Shape::convex_polygon(
+            vec![center + normal, tip, center - normal],
+            self.color,
+            Stroke::NONE,
+        ));
+        ui.painter().add(Shape::convex_polygon(
+            vec![center - normal, tail, center + normal],
+            GRAY,
+            Stroke::NONE,
+        ));
+        ui.painter().circle_filled(
+            center,
+            (self.size / 50.0).max(2.0),
+            ui.visuals().text_color(),
+        );
+    }
+
+    fn paint_lubber_mark(&self, ui: &mut Ui, rect: Rect) {
+        let radius = self.radius();
+        let half_width = (self.size / 40.0).max(3.0);
+        let top = rect.center() - Vec2::new(0.0, radius);
+        ui.painter().add(Shape::convex_polygon(
+            vec![
+                top + Vec2::new(-half_width, -1.0),
+                top + Vec2::new(half_width, -1.0),
+                top + Vec2::new(0.0, radius / 6.0),
+            ],
+            self.color,
+            Stroke::NONE,
+        ));
+    }
+
+    fn paint_wind(&self, ui: &mut Ui, rect: Rect, angle: f32) {
+        let radius = self.radius();
+        let head = (self.size / 30.0).max(3.0);
+        let tip = position_from_angle(rect, angle, radius * 0.55);
+        let tail = position_from_angle(rect, angle, radius);
+        let normal = (tail - tip).normalized().rot90() * head / 2.0;
+        let base = position_from_angle(rect, angle, radius * 0.55 + head * 1.5);
+        ui.painter()
+            .line_segment([base, tail], Stroke::new(2.0, self.wind_color));
+        ui.painter().add(Shape::convex_polygon(
+            vec![base + normal, tip, base - normal],
+            self.wind_color,
+            Stroke::NONE,
+        ));
+    }
+}
+
+impl egui::Widget for Compass {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(self.size), Sense::hover());
+
+        let heading = self.heading.rem_euclid(360.0);
+        let wind = self.wind.map(|w| w.rem_euclid(360.0));
+
+        AccessInfo {
+            typ: egui::WidgetType::ProgressIndicator,
+            role: "compass",
+            label: self.text.as_ref().map_or("", |s| s.as_str()),
+            value: heading,
+            text: Self::heading_text(heading),
+            range: 0.0..=360.0,
+            step: None,
+            unit: None,
+            alarm: self.alarm,
+            quality: self.quality,
+        }
+        .report(ui, &response);
+
+        let id = self.id.unwrap_or(response.id);
+        let display_heading = Self::animate(ui, id.with("heading"), self.damping, heading);
+        let display_wind = wind.map(|wind| Self::animate(ui, id.with("wind"), self.damping, wind));
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect, display_heading, display_wind);
+        }
+
+        response
+    }
+}
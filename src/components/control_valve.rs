@@ -0,0 +1,342 @@
+use core::fmt;
+
+use egui::{
+    pos2, vec2, Align2, Color32, FontId, Pos2, Rect, Response, Sense, Shape, Stroke, Ui, Vec2,
+};
+
+use crate::accessibility::{AccessInfo, AlarmState, Quality};
+use crate::colors::{get_text_color, GRAY, SUCCESS, WARN};
+use crate::format::{NumberFormat, ValueFormatter};
+use crate::numeric::Numeric;
+use crate::units::Unit;
+
+/// Control mode of the valve
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValveMode {
+    /// The setpoint comes from the controller
+    #[default]
+    Auto,
+    /// The setpoint is set by the operator
+    Manual,
+}
+
+/// Valve position on the loss of the actuating energy (ISA 5.1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailAction {
+    /// Fail open (FO), the arrow points away from the valve body
+    Open,
+    /// Fail closed (FC), the arrow points to the valve body
+    Closed,
+    /// Fail locked in the last position (FL)
+    Locked,
+}
+
+/// Analog (0-100%) control valve with position feedback
+pub struct ControlValve {
+    position: f64,
+    setpoint: Option<f64>,
+    mode: Option<ValveMode>,
+    fail_action: Option<FailAction>,
+    size: f32,
+    text: Option<String>,
+    color: Color32,
+    formatter: Option<Box<dyn ValueFormatter>>,
+    alarm: AlarmState,
+    quality: Quality,
+}
+
+impl ControlValve {
+    /// Create a new control valve with the position feedback in percent
+    pub fn new<V>(position: V) -> Self
+    where
+        V: Numeric,
+    {
+        Self {
+            position: position.to_f64(),
+            setpoint: None,
+            mode: None,
+            fail_action: None,
+            size: 80.0,
+            text: None,
+            color: SUCCESS,
+            formatter: None,
+            alarm: AlarmState::Normal,
+            quality: Quality::Good,
+        }
+    }
+
+    /// Set the setpoint (controller output) in percent, displayed as a marker and a readout
+    pub fn setpoint<V>(mut self, setpoint: V) -> Self
+    where
+        V: Numeric,
+    {
+        self.setpoint = Some(setpoint.to_f64());
+        self
+    }
+
+    /// Set the control mode, displayed as the A/M badge
+    pub fn mode(mut self, mode: ValveMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Set the fail action, displayed next to the actuator stem
+    pub fn fail_action(mut self, action: FailAction) -> Self {
+        self.fail_action = Some(action);
+        self
+    }
+
+    /// Set the width of the valve symbol
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the text (e.g. the valve tag), displayed below the readouts
+    pub fn text(mut self, text: impl fmt::Display) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Set the fill color of the open part of the body
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the formatter for the position and setpoint readouts (the unit symbol is appended)
+    pub fn formatter(mut self, formatter: impl ValueFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Set the alarm state, reported in the accessibility metadata
+    pub fn alarm(mut self, alarm: AlarmState) -> Self {
+        self.alarm = alarm;
+        self
+    }
+
+    /// Set the value quality, reported in the accessibility metadata
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        let value = if let Some(ref formatter) = self.formatter {
+            formatter.format(value)
+        } else {
+            NumberFormat::new().precision(1).format(value)
+        };
+        format!("{} {}", value, Unit::Percent.symbol())
+    }
+
+    fn font_size(&self) -> f32 {
+        (self.size / 7.0).max(9.0)
+    }
+
+    fn text_lines(&self) -> usize {
+        1 + usize::from(self.setpoint.is_some()) + usize::from(self.text.is_some())
+    }
+
+    /// Body (bow-tie) rect inside the symbol rect
+    fn body_rect(symbol: Rect) -> Rect {
+        let w = symbol.width();
+        Rect::from_center_size(
+            pos2(symbol.center().x, symbol.top() + w * 0.85),
+            vec2(w * 0.8, w * 0.5),
+        )
+    }
+
+    /// Vertical position of the value in the body, the open part is filled from the bottom
+    #[allow(clippy::cast_possible_truncation)]
+    fn value_y(body: Rect, value: f64) -> f32 {
+        egui::lerp(
+            body.bottom()..=body.top(),
+            (value / 100.0).clamp(0.0, 1.0) as f32,
+        )
+    }
+
+    fn paint(&self, ui: &Ui, symbol: Rect, position: f64) {
+        let painter = ui.painter();
+        let text_color = get_text_color(ui);
+        let stroke = Stroke::new((self.size / 50.0).max(1.0), text_color);
+        let w = symbol.width();
+        let body = Self::body_rect(symbol);
+        let center = body.center();
+
+        // bow-tie: two triangles, pointing to the center
+        let left = vec![body.left_top(), center, body.left_bottom()];
+        let right = vec![body.right_top(), center, body.right_bottom()];
+        let fill_clip = Rect::from_min_max(
+            pos2(body.left() - 1.0, Self::value_y(body, position)),
+            body.right_bottom() + vec2(1.0, 1.0),
+        );
+        let fill_painter = painter.with_clip_rect(fill_clip.intersect(painter.clip_rect()));
+        for triangle in [&left, &right] {
+            painter.add(Shape::convex_polygon(
+                triangle.clone(),
+                ui.visuals().extreme_bg_color,
+                Stroke::NONE,
+            ));
+            fill_painter.add(Shape::convex_polygon(
+                triangle.clone(),
+                self.color,
+                Stroke::NONE,
+            ));
+            painter.add(Shape::closed_line(triangle.clone(), stroke));
+        }
+
+        // setpoint marker, right of the body
+        if let Some(setpoint) = self.setpoint {
+            let y = Self::value_y(body, setpoint);
+            let tip = pos2(body.right() + 2.0, y);
+            let marker = w / 16.0;
+            painter.add(Shape::convex_polygon(
+                vec![
+                    tip,
+                    tip + vec2(marker * 1.5, -marker),
+                    tip + vec2(marker * 1.5, marker),
+                ],
+                WARN,
+                Stroke::NONE,
+            ));
+        }
+
+        // diaphragm actuator and stem
+        let dome_radius = w * 0.25;
+        let dome_base = pos2(symbol.center().x, symbol.top() + dome_radius + stroke.width);
+        let dome: Vec<Pos2> = (0..=32_u8)
+            .map(|i| {
+                let angle = std::f32::consts::PI * f32::from(i) / 32.0;
+                dome_base + vec2(angle.cos() * dome_radius, -angle.sin() * dome_radius)
+            })
+            .collect();
+        painter.add(Shape::closed_line(dome, stroke));
+        painter.line_segment([dome_base, center], stroke);
+
+        if let Some(action) = self.fail_action {
+            self.paint_fail_action(ui, action, dome_base, center);
+        }
+
+        if let Some(mode) = self.mode {
+            let (text, color) = match mode {
+                ValveMode::Auto => ("A", GRAY),
+                ValveMode::Manual => ("M", WARN),
+            };
+            let badge_radius = w / 10.0;
+            let badge_center = dome_base + vec2(-dome_radius - badge_radius - 2.0, -badge_radius);
+            painter.circle(badge_center, badge_radius, color, Stroke::NONE);
+            painter.text(
+                badge_center,
+                Align2::CENTER_CENTER,
+                text,
+                FontId::proportional(badge_radius * 1.4),
+                Color32::BLACK,
+            );
+        }
+    }
+
+    fn paint_fail_action(&self, ui: &Ui, action: FailAction, top: Pos2, bottom: Pos2) {
+        let painter = ui.painter();
+        let color = get_text_color(ui);
+        let stroke = Stroke::new(1.0, color);
+        let x = top.x + self.size / 10.0;
+        let (y0, y1) = (top.y + 2.0, bottom.y - self.size / 4.0 - 2.0);
+        let head = self.size / 20.0;
+        let label = match action {
+            FailAction::Open | FailAction::Closed => {
+                // the arrow points in the direction the stem moves on failure: up opens the valve
+                let (from, to) = if action == FailAction::Open {
+                    (pos2(x, y1), pos2(x, y0))
+                } else {
+                    (pos2(x, y0), pos2(x, y1))
+                };
+                let dir = (to - from).normalized();
+                painter.line_segment([from, to], stroke);
+                painter.add(Shape::convex_polygon(
+                    vec![
+                        to,
+                        to - dir * head * 1.5 + dir.rot90() * head,
+                        to - dir * head * 1.5 - dir.rot90() * head,
+                    ],
+                    color,
+                    Stroke::NONE,
+                ));
+                if action == FailAction::Open {
+                    "FO"
+                } else {
+                    "FC"
+                }
+            }
+            FailAction::Locked => {
+                let mid = egui::lerp(y0..=y1, 0.5);
+                for dy in [-head / 2.0, head / 2.0] {
+                    painter
+                        .line_segment([pos2(x - head, mid + dy), pos2(x + head, mid + dy)], stroke);
+                }
+                "FL"
+            }
+        };
+        painter.text(
+            pos2(x + head * 1.5, egui::lerp(y0..=y1, 0.5)),
+            Align2::LEFT_CENTER,
+            label,
+            FontId::proportional(self.font_size() * 0.8),
+            color,
+        );
+    }
+}
+
+impl egui::Widget for ControlValve {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let font_size = self.font_size();
+        #[allow(clippy::cast_precision_loss)]
+        let text_height = self.text_lines() as f32 * font_size * 1.2;
+        let symbol_size = vec2(self.size, self.size * 1.15);
+        let (rect, response) =
+            ui.allocate_exact_size(symbol_size + Vec2::new(0.0, text_height), Sense::hover());
+        let position = self.position.clamp(0.0, 100.0);
+
+        AccessInfo {
+            typ: egui::WidgetType::ProgressIndicator,
+            role: "control valve",
+            label: self.text.as_ref().map_or("", |s| s.as_str()),
+            value: position,
+            text: self.format_value(position),
+            range: 0.0..=100.0,
+            step: None,
+            unit: None,
+            alarm: self.alarm,
+            quality: self.quality,
+        }
+        .report(ui, &response);
+
+        if ui.is_rect_visible(rect) {
+            let symbol = Rect::from_min_size(rect.min, symbol_size);
+            self.paint(ui, symbol, position);
+
+            let text_color = get_text_color(ui);
+            let mut lines = vec![(self.format_value(position), text_color)];
+            if let Some(setpoint) = self.setpoint {
+                lines.push((format!("SP {}", self.format_value(setpoint)), WARN));
+            }
+            if let Some(ref text) = self.text {
+                lines.push((text.clone(), text_color));
+            }
+            let mut y = symbol.bottom();
+            for (line, color) in lines {
+                ui.painter().text(
+                    pos2(rect.center().x, y),
+                    Align2::CENTER_TOP,
+                    line,
+                    FontId::proportional(font_size),
+                    color,
+                );
+                y += font_size * 1.2;
+            }
+        }
+
+        response
+    }
+}
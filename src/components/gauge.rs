@@ -1,58 +1,205 @@
 use egui::{
-    epaint::PathShape, vec2, Align2, Color32, FontId, Pos2, Rect, Response, Sense, Shape, Stroke,
-    Ui,
+    emath::Rot2,
+    epaint::{PathShape, TextShape},
+    vec2, Color32, FontId, Galley, Id, Pos2, Rect, Response, Sense, Shape, Stroke, Ui, Vec2,
 };
 
-use crate::colors::{get_text_color, GRAY, GRAY_DARK, SUCCESS};
+use crate::accessibility::{AccessInfo, AlarmState, Quality};
+use crate::animation::{animate_value, Damping};
+use crate::colors::{get_text_color, GRAY, SUCCESS};
+use crate::format::{NumberFormat, ValueFormatter};
+use crate::numeric::Numeric;
+use crate::peak::{PeakHold, PeakHoldConfig, PeakState};
+use crate::scale::Scale;
+use crate::ticks::Ticks;
+use crate::units::{Unit, UnitConversion, UnitSystem};
 use core::fmt;
-use std::f32::consts::PI;
+use std::collections::HashMap;
 use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// Orientation of the gauge tick labels
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TickLabelOrientation {
+    /// Labels are always horizontal
+    #[default]
+    Horizontal,
+    /// Labels are rotated along the arc
+    Tangential,
+}
+
+/// Gauge face style, sets the defaults of the arc width, the cap style and the needle
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GaugeStyle {
+    /// Thin arc with a line needle
+    #[default]
+    Standard,
+    /// Classic analog instrument: filled dial with a bezel, tapered needle and a large hub
+    Classic,
+    /// Thick ring with rounded caps and no needle
+    Modern,
+    /// Thin arc with a triangle needle and no hub
+    Minimal,
+}
+
+/// Gauge needle shape
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NeedleShape {
+    /// Straight line
+    Line,
+    /// Tapered needle with a short tail
+    Tapered,
+    /// Triangle
+    Triangle,
+}
+
+/// Gauge arc cap style
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArcCap {
+    /// Flat ends
+    Butt,
+    /// Rounded ends
+    Round,
+}
+
+/// Text line of the gauge
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GaugeText {
+    /// Inner text, centered at the hub by default
+    Text,
+    /// Title, above the hub
+    Title,
+    /// Large value readout, below the hub
+    Value,
+    /// Units, below the value readout
+    Units,
+    /// Status line, at the bottom
+    Status,
+}
+
+impl GaugeText {
+    /// Default position relative to the dial center, in fractions of the arc radius
+    fn default_position(self) -> Vec2 {
+        match self {
+            GaugeText::Text => Vec2::ZERO,
+            GaugeText::Title => vec2(0.0, -0.4),
+            GaugeText::Value => vec2(0.0, 0.3),
+            GaugeText::Units => vec2(0.0, 0.55),
+            GaugeText::Status => vec2(0.0, 0.8),
+        }
+    }
+
+    /// Default font size, in fractions of the gauge width
+    fn default_font_size(self) -> f32 {
+        match self {
+            GaugeText::Text => 1.0 / 9.0,
+            GaugeText::Title | GaugeText::Units => 1.0 / 12.0,
+            GaugeText::Value => 1.0 / 6.0,
+            GaugeText::Status => 1.0 / 14.0,
+        }
+    }
+}
 
 /// Gauge component
 pub struct Gauge {
     value: f64,
+    integral: bool,
     value_range: RangeInclusive<f64>,
     size: f32,
-    angle_range: RangeInclusive<i16>,
-    stroke_width: f32,
+    angle_range: RangeInclusive<f32>,
+    style: GaugeStyle,
+    stroke_width: Option<f32>,
+    arc_cap: Option<ArcCap>,
     text: Option<String>,
+    title: Option<String>,
+    value_readout: bool,
+    units_text: Option<String>,
+    status: Option<String>,
+    status_color: Option<Color32>,
+    text_fonts: HashMap<GaugeText, FontId>,
+    text_positions: HashMap<GaugeText, Vec2>,
     bg_color: Option<Color32>,
     fg_color: Color32,
+    negative_color: Option<Color32>,
+    origin: Option<f64>,
     text_color: Option<Color32>,
-    arrow_length_factor: f32,
+    arrow_length_factor: Option<f32>,
     arrow_width: f32,
+    needle: Option<NeedleShape>,
+    needle_color: Option<Color32>,
     ticks: usize,
+    minor_ticks: usize,
     tick_size: f32,
+    tick_label_radius: f32,
+    tick_label_orientation: TickLabelOrientation,
     pointer_radius: f32,
+    formatter: Option<Box<dyn ValueFormatter>>,
+    scale: Scale,
+    unit: Option<Unit>,
+    unit_system: Option<UnitSystem>,
+    id: Option<Id>,
+    peak_hold: Option<PeakHoldConfig>,
+    damping: Option<Damping>,
+    alarm: AlarmState,
+    quality: Quality,
 }
 
 impl Gauge {
     /// Create a new gauge
     pub fn new<V>(value: V) -> Self
     where
-        V: Into<f64>,
+        V: Numeric,
     {
         Self {
-            value: value.into(),
+            value: value.to_f64(),
+            integral: V::INTEGRAL,
             value_range: 0.0..=100.0,
             size: 200.0,
-            angle_range: 0..=180,
-            stroke_width: 1.5,
+            angle_range: 0.0..=180.0,
+            style: GaugeStyle::Standard,
+            stroke_width: None,
+            arc_cap: None,
             text: None,
+            title: None,
+            value_readout: false,
+            units_text: None,
+            status: None,
+            status_color: None,
+            text_fonts: HashMap::new(),
+            text_positions: HashMap::new(),
             bg_color: None,
             fg_color: SUCCESS,
+            negative_color: None,
+            origin: None,
             text_color: None,
-            arrow_length_factor: 0.8,
+            arrow_length_factor: None,
             arrow_width: 3.0,
+            needle: None,
+            needle_color: None,
             ticks: 9,
+            minor_ticks: 0,
             tick_size: 3.0,
+            tick_label_radius: 1.2,
+            tick_label_orientation: TickLabelOrientation::Horizontal,
             pointer_radius: 3.0,
+            formatter: None,
+            scale: Scale::Linear,
+            unit: None,
+            unit_system: None,
+            id: None,
+            peak_hold: None,
+            damping: None,
+            alarm: AlarmState::Normal,
+            quality: Quality::Good,
         }
     }
 
     /// Set the range of the gauge
-    pub fn range(mut self, value_range: RangeInclusive<f64>) -> Self {
-        self.value_range = value_range;
+    pub fn range<V>(mut self, value_range: RangeInclusive<V>) -> Self
+    where
+        V: Numeric,
+    {
+        self.value_range = value_range.start().to_f64()..=value_range.end().to_f64();
         self
     }
 
@@ -62,11 +209,15 @@ impl Gauge {
         self
     }
 
-    /// Set the angle range of the gauge (how long is the curve and its direction)
-    pub fn angle_range(mut self, angle_range: RangeInclusive<i16>) -> Self {
-        let start = angle_range.start().clamp(&-360, &360);
-        let end = angle_range.end().clamp(&-360, &360);
-        self.angle_range = *start..=*end;
+    /// Set the angle range of the gauge in degrees (how long is the curve and its direction)
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn angle_range<V>(mut self, angle_range: RangeInclusive<V>) -> Self
+    where
+        V: Numeric,
+    {
+        let start = (angle_range.start().to_f64() as f32).clamp(-360.0, 360.0);
+        let end = (angle_range.end().to_f64() as f32).clamp(-360.0, 360.0);
+        self.angle_range = start..=end;
         self
     }
 
@@ -76,6 +227,51 @@ impl Gauge {
         self
     }
 
+    /// Set the title, displayed above the hub
+    pub fn title(mut self, title: impl fmt::Display) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Display the value readout below the hub, formatted with the tick label formatter. If the
+    /// unit is set, its symbol is displayed in the units line instead of next to the inner text
+    pub fn value_readout(mut self, value_readout: bool) -> Self {
+        self.value_readout = value_readout;
+        self
+    }
+
+    /// Set the text of the units line (the unit symbol is used by default if the value readout
+    /// is enabled)
+    pub fn units_text(mut self, text: impl fmt::Display) -> Self {
+        self.units_text = Some(text.to_string());
+        self
+    }
+
+    /// Set the status line, displayed at the bottom
+    pub fn status(mut self, status: impl fmt::Display) -> Self {
+        self.status = Some(status.to_string());
+        self
+    }
+
+    /// Set the color of the status line (the text color is used by default)
+    pub fn status_color(mut self, color: Color32) -> Self {
+        self.status_color = Some(color);
+        self
+    }
+
+    /// Set the font of the text line
+    pub fn text_font(mut self, line: GaugeText, font: FontId) -> Self {
+        self.text_fonts.insert(line, font);
+        self
+    }
+
+    /// Set the position of the text line center relative to the dial center, in fractions of the
+    /// arc radius (positive y is down)
+    pub fn text_position(mut self, line: GaugeText, position: Vec2) -> Self {
+        self.text_positions.insert(line, position);
+        self
+    }
+
     /// Set the background color of the gauge arc
     pub fn bg_color(mut self, color: Color32) -> Self {
         self.bg_color = Some(color);
@@ -88,18 +284,143 @@ impl Gauge {
         self
     }
 
+    /// Set the foreground color of the gauge arc for values below the origin (the foreground
+    /// color is used by default)
+    pub fn negative_color(mut self, color: Color32) -> Self {
+        self.negative_color = Some(color);
+        self
+    }
+
+    /// Paint the value arc from the origin value (e.g. 0 for -100..=100 ranges) instead of the
+    /// range start
+    pub fn origin<V>(mut self, origin: V) -> Self
+    where
+        V: Numeric,
+    {
+        self.origin = Some(origin.to_f64());
+        self
+    }
+
     /// Set the color of the text
     pub fn text_color(mut self, color: Color32) -> Self {
         self.text_color = Some(color);
         self
     }
 
-    /// Set the number of the ticks, number below 2 disables the ticks
+    /// Set the maximum number of the major ticks, number below 2 disables the ticks. The ticks
+    /// are placed at "nice" values (1-2-5 steps)
     pub fn ticks(mut self, n: usize) -> Self {
         self.ticks = n;
         self
     }
 
+    /// Set the number of minor tick intervals between the major ticks, number below 2 disables
+    /// the minor ticks
+    pub fn minor_ticks(mut self, n: usize) -> Self {
+        self.minor_ticks = n;
+        self
+    }
+
+    /// Set the radius of the tick labels, relative to the arc radius (1.2 by default, values
+    /// below 1.0 put the labels inside the arc)
+    pub fn tick_label_radius(mut self, factor: f32) -> Self {
+        self.tick_label_radius = factor.max(0.0);
+        self
+    }
+
+    /// Set the orientation of the tick labels
+    pub fn tick_label_orientation(mut self, orientation: TickLabelOrientation) -> Self {
+        self.tick_label_orientation = orientation;
+        self
+    }
+
+    /// Set the formatter for the tick labels
+    pub fn formatter(mut self, formatter: impl ValueFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// Set the value scale (linear by default)
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the engineering unit of the value and the range, the unit symbol is displayed next to
+    /// the inner text
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Display the value, the range and the ticks in the target unit system (requires the unit to
+    /// be set)
+    pub fn unit_system(mut self, system: UnitSystem) -> Self {
+        self.unit_system = Some(system);
+        self
+    }
+
+    /// Set the gauge id, required to keep the peak-hold markers of gauges with changing layout
+    /// positions and to reset them with [`reset_peak_hold`](crate::reset_peak_hold)
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Show the peak (max) marker as a ghost needle. The marker is reset by double click
+    pub fn peak_hold(mut self, mode: PeakHold) -> Self {
+        self.peak_hold = Some(PeakHoldConfig { mode, min: false });
+        self
+    }
+
+    /// Show the min and max markers as ghost needles. The markers are reset by double click
+    pub fn min_max_hold(mut self, mode: PeakHold) -> Self {
+        self.peak_hold = Some(PeakHoldConfig { mode, min: true });
+        self
+    }
+
+    /// Animate the arc and the arrow, easing them toward the new values
+    pub fn damping(mut self, damping: Damping) -> Self {
+        self.damping = Some(damping);
+        self
+    }
+
+    /// Set the alarm state, reported in the accessibility metadata
+    pub fn alarm(mut self, alarm: AlarmState) -> Self {
+        self.alarm = alarm;
+        self
+    }
+
+    /// Set the value quality, reported in the accessibility metadata
+    pub fn quality(mut self, quality: Quality) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    fn apply_units(&mut self) {
+        let Some(conversion) = UnitConversion::new(self.unit, self.unit_system) else {
+            return;
+        };
+        // converted values are not integral anymore
+        self.integral &= Some(conversion.unit()) == self.unit;
+        self.value = conversion.apply(self.value);
+        self.value_range =
+            conversion.apply(*self.value_range.start())..=conversion.apply(*self.value_range.end());
+        self.origin = self.origin.map(|v| conversion.apply(v));
+        self.scale.convert_values(|v| conversion.apply(v));
+        let symbol = conversion.unit().symbol();
+        if self.value_readout {
+            if self.units_text.is_none() {
+                self.units_text = Some(symbol.to_owned());
+            }
+        } else {
+            self.text = Some(match self.text.take() {
+                Some(text) => format!("{} {}", text, symbol),
+                None => symbol.to_owned(),
+            });
+        }
+    }
+
     /// Set the pointer radius
     pub fn pointer_radius(mut self, size: f32) -> Self {
         self.pointer_radius = size.max(1.0);
@@ -114,12 +435,104 @@ impl Gauge {
         }
     }
 
-    /// Set the stroke width of the gauge arc
+    /// Set the face style of the gauge
+    pub fn style(mut self, style: GaugeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the stroke width of the gauge arc (overrides the style default)
     pub fn stroke_width(mut self, stroke_width: f32) -> Self {
-        self.stroke_width = stroke_width;
+        self.stroke_width = Some(stroke_width);
+        self
+    }
+
+    /// Set the cap style of the gauge arc (overrides the style default)
+    pub fn arc_cap(mut self, cap: ArcCap) -> Self {
+        self.arc_cap = Some(cap);
+        self
+    }
+
+    /// Set the needle shape (overrides the style default)
+    pub fn needle(mut self, shape: NeedleShape) -> Self {
+        self.needle = Some(shape);
+        self
+    }
+
+    /// Set the color of the needle and the hub (the theme text color is used by default)
+    pub fn needle_color(mut self, color: Color32) -> Self {
+        self.needle_color = Some(color);
         self
     }
 
+    fn arc_width(&self) -> f32 {
+        self.stroke_width.unwrap_or_else(|| match self.style {
+            GaugeStyle::Standard => 1.5,
+            GaugeStyle::Classic => 2.0,
+            GaugeStyle::Modern => self.gauge_width() / 12.0,
+            GaugeStyle::Minimal => 1.0,
+        })
+    }
+
+    fn cap(&self) -> ArcCap {
+        self.arc_cap.unwrap_or(match self.style {
+            GaugeStyle::Modern => ArcCap::Round,
+            _ => ArcCap::Butt,
+        })
+    }
+
+    fn needle_shape(&self) -> NeedleShape {
+        self.needle.unwrap_or(match self.style {
+            GaugeStyle::Standard | GaugeStyle::Modern => NeedleShape::Line,
+            GaugeStyle::Classic => NeedleShape::Tapered,
+            GaugeStyle::Minimal => NeedleShape::Triangle,
+        })
+    }
+
+    fn needle_length_factor(&self) -> f32 {
+        self.arrow_length_factor.unwrap_or(match self.style {
+            GaugeStyle::Standard | GaugeStyle::Minimal => 0.8,
+            GaugeStyle::Classic => 0.9,
+            GaugeStyle::Modern => 0.0,
+        })
+    }
+
+    fn has_needle(&self) -> bool {
+        self.needle_length_factor() >= 0.1
+    }
+
+    fn hub_radius(&self) -> f32 {
+        match self.style {
+            GaugeStyle::Standard | GaugeStyle::Modern => self.pointer_radius * 0.8,
+            GaugeStyle::Classic => self.pointer_radius * 1.6,
+            GaugeStyle::Minimal => 0.0,
+        }
+    }
+
+    fn bezel_width(&self) -> f32 {
+        (self.size / 60.0).max(2.0)
+    }
+
+    /// Radius of the filled dial of the classic style, covers the arc and the tick labels
+    fn face_radius(&self) -> f32 {
+        let radius = if self.ticks > 1 {
+            self.radius() * self.tick_label_radius.max(1.0) + self.gauge_width() / 15.0
+        } else {
+            self.radius() + self.arc_width() / 2.0 + self.pointer_radius
+        };
+        radius.min(self.size / 2.0 - self.bezel_width())
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        if let Some(ref formatter) = self.formatter {
+            formatter.format(value)
+        } else if self.integral && value.fract() == 0.0 {
+            NumberFormat::new().precision(0).format(value)
+        } else {
+            NumberFormat::new().format(value)
+        }
+    }
+
     fn text_clearance(&self) -> f32 {
         self.size / 10.0
     }
@@ -129,29 +542,24 @@ impl Gauge {
     }
 
     #[allow(clippy::cast_possible_truncation)]
-    fn value_to_angle(&self, v: f64) -> i16 {
+    fn value_to_angle(&self, v: f64) -> f32 {
         let max_angle = *self.angle_range.end();
         let min_angle = *self.angle_range.start();
-        let angle_range = f64::from(max_angle - min_angle);
-        let min_value = self.value_range.start();
-        let max_value = self.value_range.end();
-        let normalized = (v - min_value) / (max_value - min_value);
-        (f64::from(max_angle) - (normalized * angle_range)) as i16
+        let normalized =
+            self.scale
+                .normalize(v, *self.value_range.start(), *self.value_range.end());
+        max_angle - normalized as f32 * (max_angle - min_angle)
     }
 
-    /// Set the arrow length factor, a factor < 0.1 disables the arrow
+    /// Set the arrow length factor, a factor < 0.1 disables the arrow (overrides the style
+    /// default)
     pub fn arrow_length_factor(mut self, factor: f32) -> Self {
-        self.arrow_length_factor = factor.clamp(0., 1.3);
+        self.arrow_length_factor = Some(factor.clamp(0., 1.3));
         self
     }
 
-    fn paint(&mut self, ui: &mut Ui, outer_rect: Rect, value: f64) {
-        let rect = if self.ticks > 1 {
-            outer_rect.shrink(self.text_clearance())
-        } else {
-            outer_rect
-        };
-
+    /// Paints the gauge, the rect is the square of the gauge width, centered at the dial center
+    fn paint(&mut self, ui: &mut Ui, rect: Rect, value: f64, peaks: Option<PeakState>) {
         let min_angle = *self.angle_range.start();
         let max_angle = *self.angle_range.end();
         let current_angle = self.value_to_angle(value);
@@ -162,160 +570,472 @@ impl Gauge {
             ui.style().visuals.clone().extreme_bg_color
         };
 
+        let origin = self.origin.unwrap_or(*self.value_range.start());
+        let origin_angle = self.value_to_angle(origin);
+        let fg_color = if value < origin {
+            self.negative_color.unwrap_or(self.fg_color)
+        } else {
+            self.fg_color
+        };
+
+        if self.style == GaugeStyle::Classic {
+            self.paint_face(ui, rect);
+        }
+
         self.paint_arc(ui, rect, min_angle, max_angle, bg_color);
-        self.paint_arc(ui, rect, current_angle, max_angle, self.fg_color);
+        self.paint_arc(
+            ui,
+            rect,
+            current_angle.min(origin_angle),
+            current_angle.max(origin_angle),
+            fg_color,
+        );
 
-        if self.arrow_length_factor < 0.1 {
-            self.paint_point(ui, rect, current_angle);
-            self.paint_point(ui, rect, max_angle);
+        if !self.has_needle() && self.cap() == ArcCap::Butt {
+            self.paint_point(ui, rect, current_angle, fg_color);
+            self.paint_point(ui, rect, origin_angle, fg_color);
         }
 
         if self.ticks >= 2 {
             self.paint_ticks(ui, rect);
         }
 
-        if self.arrow_length_factor >= 0.1 {
-            self.paint_arrow(ui, rect, current_angle);
+        if let (Some(peaks), Some(config)) = (peaks, self.peak_hold) {
+            self.paint_ghost(ui, rect, self.value_to_angle(peaks.max));
+            if config.min {
+                self.paint_ghost(ui, rect, self.value_to_angle(peaks.min));
+            }
         }
 
-        if let Some(ref text) = self.text {
-            self.paint_text(ui, rect, text);
+        if self.has_needle() {
+            self.paint_arrow(ui, rect, current_angle);
         }
+
+        self.paint_text(ui, rect);
     }
 
-    fn paint_arc(&self, ui: &mut Ui, rect: Rect, start_angle: i16, end_angle: i16, color: Color32) {
+    fn paint_arc(&self, ui: &mut Ui, rect: Rect, start_angle: f32, end_angle: f32, color: Color32) {
         if start_angle >= end_angle {
             return;
         }
 
-        let step = ((end_angle - start_angle) / 30).max(1);
-        let mut points = Vec::with_capacity(
-            usize::try_from(end_angle - start_angle).unwrap() / usize::try_from(step).unwrap(),
-        );
-
-        let mut angle = start_angle;
-        while angle <= end_angle {
-            points.push(position_from_angle(rect, angle, self.radius()));
-            angle += step;
-        }
-
-        if angle - step < end_angle {
-            points.push(position_from_angle(rect, end_angle, self.radius()));
-        }
+        ui.painter().add(Shape::Path(PathShape {
+            points: arc_points(ui, rect, self.radius(), start_angle, end_angle),
+            closed: false,
+            fill: Color32::TRANSPARENT,
+            stroke: Stroke::new(self.arc_width(), color).into(),
+        }));
 
-        if !points.is_empty() {
-            ui.painter().add(Shape::Path(PathShape {
-                points,
-                closed: false,
-                fill: Color32::TRANSPARENT,
-                stroke: Stroke::new(self.stroke_width, color).into(),
-            }));
+        if self.cap() == ArcCap::Round {
+            for angle in [start_angle, end_angle] {
+                ui.painter().circle_filled(
+                    position_from_angle(rect, angle, self.radius()),
+                    self.arc_width() / 2.0,
+                    color,
+                );
+            }
         }
     }
 
-    fn paint_ticks(&self, ui: &mut Ui, rect: Rect) {
-        let text_color = self.text_color.unwrap_or_else(|| {
+    fn tick_color(&self, ui: &Ui) -> Color32 {
+        self.text_color.unwrap_or_else(|| {
             if ui.visuals().dark_mode {
                 GRAY
             } else {
                 Color32::GRAY
             }
-        });
+        })
+    }
 
-        let value_range = *self.value_range.end() - *self.value_range.start();
-        #[allow(clippy::cast_precision_loss)]
-        let step = value_range / (self.ticks - 1) as f64;
-        let font_size = self.gauge_width() / 15.0;
+    fn scale_ticks(&self) -> Ticks {
+        let min_value = *self.value_range.start();
+        let max_value = *self.value_range.end();
+        let mut ticks = Ticks::new(
+            &self.scale,
+            min_value,
+            max_value,
+            self.ticks,
+            self.minor_ticks,
+            self.integral,
+        );
+        if self.angle_range.end() - self.angle_range.start() >= 360.0 {
+            // the last tick matches the first one
+            let eps = (max_value - min_value).abs() * 1e-9;
+            ticks.major.retain(|v| (v - max_value).abs() > eps);
+        }
+        ticks
+    }
 
-        for i in 0..self.ticks {
-            if i == self.ticks - 1 && self.angle_range.end() - self.angle_range.start() >= 360 {
+    fn tick_label(&self, ui: &Ui, rect: Rect, tick_value: f64, color: Color32) -> TickLabel {
+        let angle = self.value_to_angle(tick_value);
+        let center = position_from_angle(rect, angle, self.radius() * self.tick_label_radius);
+        let galley = ui.painter().layout_no_wrap(
+            self.format_value(tick_value),
+            FontId::proportional(self.gauge_width() / 15.0),
+            color,
+        );
+        let size = galley.size();
+        let rotation = match self.tick_label_orientation {
+            TickLabelOrientation::Horizontal => 0.0,
+            TickLabelOrientation::Tangential => tangential_rotation(angle),
+        };
+        let (sin, cos) = rotation.sin_cos();
+        let bounding_size = vec2(
+            (size.x * cos).abs() + (size.y * sin).abs(),
+            (size.x * sin).abs() + (size.y * cos).abs(),
+        );
+        TickLabel {
+            pos: center - Rot2::from_angle(rotation) * (size / 2.0),
+            galley,
+            rotation,
+            rect: Rect::from_center_size(center, bounding_size),
+        }
+    }
+
+    fn paint_ticks(&self, ui: &mut Ui, rect: Rect) {
+        let text_color = self.tick_color(ui);
+        let ticks = self.scale_ticks();
+
+        for tick_value in ticks.minor {
+            let angle = self.value_to_angle(tick_value);
+            let tick_inner = position_from_angle(rect, angle, self.radius() - self.tick_size / 2.0);
+            let tick_outer = position_from_angle(rect, angle, self.radius() + self.tick_size / 2.0);
+            ui.painter()
+                .line_segment([tick_inner, tick_outer], Stroke::new(0.5, text_color));
+        }
+
+        let mut label_rects: Vec<Rect> = Vec::with_capacity(ticks.major.len());
+        for tick_value in ticks.major {
+            let angle = self.value_to_angle(tick_value);
+
+            let tick_inner = position_from_angle(rect, angle, self.radius() - self.tick_size);
+            let tick_outer = position_from_angle(rect, angle, self.radius() + self.tick_size);
+            ui.painter()
+                .line_segment([tick_inner, tick_outer], Stroke::new(1.0, text_color));
+
+            let label = self.tick_label(ui, rect, tick_value, text_color);
+            if label_rects.iter().any(|r| r.intersects(label.rect)) {
                 continue;
             }
-            #[allow(clippy::cast_precision_loss)]
-            let tick_value = *self.value_range.start() + step * i as f64;
-            let angle = self.value_to_angle(tick_value);
+            label_rects.push(label.rect.expand(1.0));
+            ui.painter().add(
+                TextShape::new(label.pos, label.galley, text_color).with_angle(label.rotation),
+            );
+        }
+    }
 
-            if self.ticks >= 2 {
-                let tick_inner = position_from_angle(rect, angle, self.radius() - self.tick_size);
-                let tick_outer = position_from_angle(rect, angle, self.radius() + self.tick_size);
-                ui.painter()
-                    .line_segment([tick_inner, tick_outer], Stroke::new(1.0, text_color));
+    /// Bounding box of the painted gauge parts, relative to the dial center. Never exceeds the
+    /// square of the gauge size
+    fn bounds(&self, ui: &Ui) -> Rect {
+        let dial = Rect::from_center_size(Pos2::ZERO, Vec2::splat(self.gauge_width()));
+        let mut bounds = Rect::from_center_size(
+            Pos2::ZERO,
+            Vec2::splat(self.pointer_radius.max(self.hub_radius()) * 2.0),
+        );
+        let mut outer_radius = self.radius() + self.arc_width() / 2.0;
+        if self.ticks > 1 {
+            outer_radius += self.tick_size;
+        }
+        if self.style == GaugeStyle::Classic {
+            outer_radius = outer_radius.max(self.face_radius() + self.bezel_width() / 2.0);
+        }
+        let arrow_length = if self.has_needle() {
+            self.radius() * self.needle_length_factor() + self.arrow_width / 2.0
+        } else {
+            outer_radius = outer_radius.max(self.radius() + self.pointer_radius);
+            0.0
+        };
+        let (start_angle, end_angle) = (*self.angle_range.start(), *self.angle_range.end());
+        // sample the arc each degree
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = (end_angle - start_angle).abs().ceil().max(1.0) as usize;
+        for i in 0..=samples {
+            #[allow(clippy::cast_precision_loss)]
+            let angle = egui::lerp(start_angle..=end_angle, i as f32 / samples as f32);
+            bounds.extend_with(position_from_angle(dial, angle, outer_radius));
+            bounds.extend_with(position_from_angle(dial, angle, arrow_length));
+        }
+        if self.ticks > 1 {
+            for tick_value in self.scale_ticks().major {
+                bounds = bounds.union(
+                    self.tick_label(ui, dial, tick_value, Color32::PLACEHOLDER)
+                        .rect,
+                );
             }
+        }
+        for (text_rect, _) in self.text_lines(ui) {
+            bounds = bounds.union(text_rect);
+        }
+        bounds
+            .expand(1.0)
+            .intersect(Rect::from_center_size(Pos2::ZERO, Vec2::splat(self.size)))
+    }
 
-            let text_pos =
-                position_from_angle(rect, angle, self.radius() + self.gauge_width() * 0.1);
-            ui.painter().text(
-                text_pos,
-                Align2::CENTER_CENTER,
-                format!("{}", tick_value.round()),
-                FontId::proportional(font_size),
-                text_color,
-            );
+    fn paint_face(&self, ui: &mut Ui, rect: Rect) {
+        let center = rect.center();
+        let radius = self.face_radius();
+        let fill = ui.visuals().faint_bg_color;
+        let stroke = Stroke::new(
+            self.bezel_width(),
+            ui.visuals().widgets.noninteractive.bg_stroke.color,
+        );
+        let (start_angle, end_angle) = (*self.angle_range.start(), *self.angle_range.end());
+        if end_angle - start_angle >= 180.0 {
+            ui.painter().circle(center, radius, fill, stroke);
+        } else {
+            // sectors up to a half circle are convex
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let samples = (end_angle - start_angle).ceil().max(1.0) as usize;
+            let mut points = Vec::with_capacity(samples + 2);
+            points.push(center);
+            for i in 0..=samples {
+                #[allow(clippy::cast_precision_loss)]
+                let angle = egui::lerp(start_angle..=end_angle, i as f32 / samples as f32);
+                points.push(position_from_angle(rect, angle, radius));
+            }
+            ui.painter()
+                .add(Shape::convex_polygon(points, fill, stroke));
         }
     }
 
-    fn paint_arrow(&self, ui: &mut Ui, rect: Rect, angle: i16) {
+    fn paint_arrow(&self, ui: &mut Ui, rect: Rect, angle: f32) {
         let center = rect.center();
-        let arrow_color = GRAY_DARK;
+        let color = self
+            .needle_color
+            .unwrap_or_else(|| ui.visuals().text_color());
 
-        let arrow_length = self.radius() * self.arrow_length_factor;
+        let arrow_length = self.radius() * self.needle_length_factor();
         let arrow_end = position_from_angle(rect, angle, arrow_length);
+        let dir = (arrow_end - center).normalized();
+        let normal = dir.rot90();
+        let width = self.arrow_width;
 
-        ui.painter().line_segment(
-            [center, arrow_end],
-            Stroke::new(self.arrow_width, arrow_color),
-        );
+        match self.needle_shape() {
+            NeedleShape::Line => {
+                ui.painter()
+                    .line_segment([center, arrow_end], Stroke::new(width, color));
+            }
+            NeedleShape::Tapered => {
+                let tail = center - dir * arrow_length * 0.15;
+                ui.painter().add(Shape::convex_polygon(
+                    vec![
+                        tail + normal * width,
+                        arrow_end + normal * width * 0.2,
+                        arrow_end - normal * width * 0.2,
+                        tail - normal * width,
+                    ],
+                    color,
+                    Stroke::NONE,
+                ));
+            }
+            NeedleShape::Triangle => {
+                ui.painter().add(Shape::convex_polygon(
+                    vec![
+                        center + normal * width * 1.2,
+                        arrow_end,
+                        center - normal * width * 1.2,
+                    ],
+                    color,
+                    Stroke::NONE,
+                ));
+            }
+        }
 
-        ui.painter()
-            .circle(center, self.pointer_radius * 0.8, arrow_color, Stroke::NONE);
+        let hub_radius = self.hub_radius();
+        if hub_radius > 0.0 {
+            let hub_stroke = if self.style == GaugeStyle::Classic {
+                Stroke::new(1.0, ui.visuals().widgets.noninteractive.bg_stroke.color)
+            } else {
+                Stroke::NONE
+            };
+            ui.painter().circle(center, hub_radius, color, hub_stroke);
+        }
+    }
+
+    fn paint_ghost(&self, ui: &mut Ui, rect: Rect, angle: f32) {
+        if self.has_needle() {
+            let arrow_end =
+                position_from_angle(rect, angle, self.radius() * self.needle_length_factor());
+            ui.painter().line_segment(
+                [rect.center(), arrow_end],
+                Stroke::new(self.arrow_width / 2.0, GRAY.gamma_multiply(0.6)),
+            );
+        } else {
+            let marker_size = self.arc_width().max(self.tick_size) + 2.0;
+            let inner = position_from_angle(rect, angle, self.radius() - marker_size);
+            let outer = position_from_angle(rect, angle, self.radius() + marker_size);
+            ui.painter().line_segment(
+                [inner, outer],
+                Stroke::new(1.5, self.text_color.unwrap_or_else(|| get_text_color(ui))),
+            );
+        }
     }
 
-    fn paint_text(&self, ui: &mut Ui, rect: Rect, text: &str) {
+    /// Laid out text lines, the rects are relative to the dial center
+    fn text_lines(&self, ui: &Ui) -> Vec<(Rect, Arc<Galley>)> {
         let text_color = self.text_color.unwrap_or_else(|| get_text_color(ui));
+        let value = self.value_readout.then(|| self.format_value(self.value));
+        [
+            (GaugeText::Text, self.text.as_ref(), text_color),
+            (GaugeText::Title, self.title.as_ref(), text_color),
+            (GaugeText::Value, value.as_ref(), text_color),
+            (GaugeText::Units, self.units_text.as_ref(), text_color),
+            (
+                GaugeText::Status,
+                self.status.as_ref(),
+                self.status_color.unwrap_or(text_color),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(line, text, color)| {
+            let text = text?;
+            let font = self.text_fonts.get(&line).cloned().unwrap_or_else(|| {
+                FontId::proportional(self.gauge_width() * line.default_font_size())
+            });
+            let position = self
+                .text_positions
+                .get(&line)
+                .copied()
+                .unwrap_or_else(|| line.default_position());
+            let galley = ui.painter().layout_no_wrap(text.clone(), font, color);
+            let text_rect =
+                Rect::from_center_size((position * self.radius()).to_pos2(), galley.size());
+            Some((text_rect, galley))
+        })
+        .collect()
+    }
 
-        ui.painter().text(
-            rect.center(),
-            Align2::CENTER_CENTER,
-            text,
-            FontId::proportional(self.gauge_width() / 9.0),
-            text_color,
-        );
+    fn paint_text(&self, ui: &mut Ui, rect: Rect) {
+        for (text_rect, galley) in self.text_lines(ui) {
+            let pos = text_rect.min + rect.center().to_vec2();
+            ui.painter().galley(pos, galley, Color32::PLACEHOLDER);
+        }
     }
 
-    fn paint_point(&self, ui: &mut Ui, rect: Rect, angle: i16) {
-        let point = position_from_angle(rect, angle, self.radius() - self.stroke_width / 2.0);
-        ui.painter().circle(
-            point,
-            self.pointer_radius,
-            self.fg_color,
-            Stroke::new(1.0, self.fg_color),
-        );
+    fn paint_point(&self, ui: &mut Ui, rect: Rect, angle: f32, color: Color32) {
+        let point = position_from_angle(rect, angle, self.radius() - self.arc_width() / 2.0);
+        ui.painter()
+            .circle(point, self.pointer_radius, color, Stroke::new(1.0, color));
     }
 }
 
 impl egui::Widget for Gauge {
     fn ui(mut self, ui: &mut Ui) -> Response {
-        let desired_size = vec2(self.size, self.size);
-        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+        self.apply_units();
+        let bounds = self.bounds(ui);
+        let sense = if self.peak_hold.is_some() {
+            Sense::click()
+        } else {
+            Sense::hover()
+        };
+        let (rect, response) = ui.allocate_exact_size(bounds.size(), sense);
+        let dial_rect = Rect::from_center_size(
+            rect.min - bounds.min.to_vec2(),
+            Vec2::splat(self.gauge_width()),
+        );
 
         let value = self
             .value
             .clamp(*self.value_range.start(), *self.value_range.end());
 
-        response.widget_info(|| {
-            egui::WidgetInfo::slider(true, value, self.text.as_ref().map_or("", |s| s.as_str()))
+        let peaks = self.peak_hold.map(|config| {
+            PeakState::update(
+                ui,
+                &response,
+                self.id.unwrap_or(response.id),
+                config,
+                value,
+                self.value_range.end() - self.value_range.start(),
+            )
         });
 
+        AccessInfo {
+            typ: egui::WidgetType::ProgressIndicator,
+            role: "gauge",
+            label: self
+                .title
+                .as_ref()
+                .or(self.text.as_ref())
+                .map_or("", |s| s.as_str()),
+            value,
+            text: self.format_value(value),
+            range: self.value_range.clone(),
+            step: None,
+            unit: UnitConversion::new(self.unit, self.unit_system).map(UnitConversion::unit),
+            alarm: self.alarm,
+            quality: self.quality,
+        }
+        .report(ui, &response);
+
+        let display_value = if let Some(damping) = self.damping {
+            animate_value(
+                ui,
+                self.id.unwrap_or(response.id),
+                damping,
+                value,
+                self.value_range.end() - self.value_range.start(),
+            )
+        } else {
+            value
+        };
+
         if ui.is_rect_visible(rect) {
-            self.paint(ui, rect, value);
+            self.paint(ui, dial_rect, display_value, peaks);
         }
 
         response
     }
 }
 
-fn position_from_angle(rect: Rect, angle: i16, radius: f32) -> Pos2 {
+struct TickLabel {
+    pos: Pos2,
+    galley: Arc<Galley>,
+    rotation: f32,
+    rect: Rect,
+}
+
+/// Rotation of a label along the arc, kept readable (never upside down)
+fn tangential_rotation(angle: f32) -> f32 {
+    let rotation = (90.0 - angle).rem_euclid(360.0);
+    let rotation = if rotation > 270.0 {
+        rotation - 360.0
+    } else if rotation > 90.0 {
+        rotation - 180.0
+    } else {
+        rotation
+    };
+    rotation.to_radians()
+}
+
+/// Points of the arc, the number of segments adapts to the radius and the screen scale
+pub(crate) fn arc_points(
+    ui: &Ui,
+    rect: Rect,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+) -> Vec<Pos2> {
+    /// Max distance between the arc and its segments, in physical pixels
+    const TOLERANCE: f32 = 0.1;
+
+    let radius_px = (radius * ui.ctx().pixels_per_point()).max(1.0);
+    let max_segment_angle = 2.0 * (1.0 - (TOLERANCE / radius_px).min(1.0)).acos();
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let segments = ((end_angle - start_angle).abs().to_radians() / max_segment_angle)
+        .ceil()
+        .clamp(1.0, 1024.0) as usize;
+
+    #[allow(clippy::cast_precision_loss)]
+    (0..=segments)
+        .map(|i| {
+            let angle = egui::lerp(start_angle..=end_angle, i as f32 / segments as f32);
+            position_from_angle(rect, angle, radius)
+        })
+        .collect()
+}
+
+pub(crate) fn position_from_angle(rect: Rect, angle: f32, radius: f32) -> Pos2 {
     let center = rect.center();
-    let angle_rad = f32::from(angle) * PI / 180.0;
+    let angle_rad = angle.to_radians();
     center + vec2(angle_rad.cos() * radius, -angle_rad.sin() * radius)
 }
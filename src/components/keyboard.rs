@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use egui::{
+    vec2, Align2, Area, Button, Context, Event, FontId, Frame, Id, Key, Modifiers, Order, Rect,
+    RichText, Ui, Vec2,
+};
+
+use crate::touch::hit_scale;
+
+/// On-screen keyboard layout
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// Numeric keypad
+    Numeric,
+    /// English alphanumeric keyboard
+    #[default]
+    Qwerty,
+    /// German alphanumeric keyboard
+    Qwertz,
+}
+
+impl KeyboardLayout {
+    /// Character rows and their shifted variants
+    fn rows(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            KeyboardLayout::Numeric => &[
+                ("789", "789"),
+                ("456", "456"),
+                ("123", "123"),
+                ("0.-", "0.-"),
+            ],
+            KeyboardLayout::Qwerty => &[
+                ("1234567890-", "!@#$%^&*()_"),
+                ("qwertyuiop", "QWERTYUIOP"),
+                ("asdfghjkl;", "ASDFGHJKL:"),
+                ("zxcvbnm,./", "ZXCVBNM<>?"),
+            ],
+            KeyboardLayout::Qwertz => &[
+                ("1234567890ß", "!\"§$%&/()=?"),
+                ("qwertzuiopü", "QWERTZUIOPÜ"),
+                ("asdfghjklöä", "ASDFGHJKLÖÄ"),
+                ("yxcvbnm,.-", "YXCVBNM;:_"),
+            ],
+        }
+    }
+}
+
+/// A key, pressed on the on-screen keyboard
+enum KeyPress {
+    Text(char),
+    Key(Key),
+    Shift,
+    Caps,
+}
+
+const EVENTS_ID: &str = "ehmi_on_screen_keyboard_events";
+
+/// On-screen keyboard overlay for touch panels without a physical keyboard
+///
+/// The keyboard appears while an editable [`egui::TextEdit`] has focus and injects the pressed
+/// keys into the egui input, so the existing text fields work unchanged. Keep the keyboard in the
+/// application state and call [`OnScreenKeyboard::show`] at the end of each frame, after all
+/// panels and windows.
+pub struct OnScreenKeyboard {
+    layout: KeyboardLayout,
+    key_size: Option<f32>,
+    shift: bool,
+    caps: bool,
+    target: Option<Id>,
+    rect: Option<Rect>,
+}
+
+impl Default for OnScreenKeyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OnScreenKeyboard {
+    /// Create a new on-screen keyboard
+    pub fn new() -> Self {
+        Self {
+            layout: KeyboardLayout::Qwerty,
+            key_size: None,
+            shift: false,
+            caps: false,
+            target: None,
+            rect: None,
+        }
+    }
+
+    /// Set the keyboard layout
+    pub fn layout(mut self, layout: KeyboardLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Change the keyboard layout
+    pub fn set_layout(&mut self, layout: KeyboardLayout) {
+        self.layout = layout;
+    }
+
+    /// Set the size of the keys (40 points scaled with the touch profile by default)
+    pub fn key_size(mut self, size: f32) -> Self {
+        self.key_size = Some(size);
+        self
+    }
+
+    /// Shows the keyboard if a text field has focus, returns `true` if the keyboard is visible
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        register_injection(ctx);
+
+        let text_focused = ctx.output(|o| o.ime.is_some());
+        if text_focused {
+            self.target = ctx.memory(egui::Memory::focused);
+        } else {
+            // a press on the keyboard takes the focus from the text field, keep the keyboard
+            // until the key is released
+            let on_keyboard = ctx
+                .input(|i| i.pointer.latest_pos())
+                .zip(self.rect)
+                .is_some_and(|(pos, rect)| rect.contains(pos));
+            if !on_keyboard {
+                self.target = None;
+            }
+        }
+        let Some(target) = self.target else {
+            self.rect = None;
+            return false;
+        };
+
+        let key_size = self.key_size.unwrap_or(40.0 * hit_scale(ctx));
+        let area = Area::new(Id::new("ehmi_on_screen_keyboard"))
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_BOTTOM, vec2(0.0, -key_size / 4.0))
+            .show(ctx, |ui| {
+                Frame::popup(ui.style())
+                    .show(ui, |ui| self.keys_ui(ui, key_size))
+                    .inner
+            });
+        self.rect = Some(area.response.rect);
+
+        if let Some(press) = area.inner {
+            let event = match press {
+                KeyPress::Text(c) => {
+                    self.shift = false;
+                    Some(Event::Text(c.to_string()))
+                }
+                KeyPress::Key(key) => Some(Event::Key {
+                    key,
+                    physical_key: None,
+                    pressed: true,
+                    repeat: false,
+                    modifiers: Modifiers::NONE,
+                }),
+                KeyPress::Shift => {
+                    self.shift = !self.shift;
+                    None
+                }
+                KeyPress::Caps => {
+                    self.caps = !self.caps;
+                    None
+                }
+            };
+            if let Some(event) = event {
+                ctx.data_mut(|d| {
+                    d.get_temp_mut_or_default::<Vec<Event>>(Id::new(EVENTS_ID))
+                        .push(event);
+                });
+            }
+            ctx.memory_mut(|m| m.request_focus(target));
+            ctx.request_repaint();
+        }
+        true
+    }
+
+    fn keys_ui(&self, ui: &mut Ui, size: f32) -> Option<KeyPress> {
+        let key_size = Vec2::splat(size);
+        let spacing = ui.spacing().item_spacing.x;
+        let wide = |n: f32| vec2(size * n + spacing * (n - 1.0), size);
+        let font = FontId::proportional(size * 0.45);
+        let key = |ui: &mut Ui, size: Vec2, text: &str, selected: bool| {
+            ui.add_sized(
+                size,
+                Button::new(RichText::new(text).font(font.clone())).selected(selected),
+            )
+            .clicked()
+        };
+        let upper = self.shift != self.caps;
+        let mut pressed = None;
+        let rows = self.layout.rows();
+        for (i, (lower_row, upper_row)) in rows.iter().enumerate() {
+            ui.horizontal(|ui| {
+                for (lower, upper_c) in lower_row.chars().zip(upper_row.chars()) {
+                    // caps lock affects letters only, shift affects all the keys
+                    let c = if lower.is_alphabetic() {
+                        if upper {
+                            upper_c
+                        } else {
+                            lower
+                        }
+                    } else if self.shift {
+                        upper_c
+                    } else {
+                        lower
+                    };
+                    if key(ui, key_size, &c.to_string(), false) {
+                        pressed = Some(KeyPress::Text(c));
+                    }
+                }
+                if i == 0 && key(ui, wide(1.5), "⌫", false) {
+                    pressed = Some(KeyPress::Key(Key::Backspace));
+                }
+                if self.layout == KeyboardLayout::Numeric
+                    && i == rows.len() - 1
+                    && key(ui, key_size, "⏎", false)
+                {
+                    pressed = Some(KeyPress::Key(Key::Enter));
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            if self.layout != KeyboardLayout::Numeric {
+                if key(ui, wide(1.5), "⇧", self.shift) {
+                    pressed = Some(KeyPress::Shift);
+                }
+                if key(ui, wide(1.5), "Caps", self.caps) {
+                    pressed = Some(KeyPress::Caps);
+                }
+                if key(ui, wide(4.0), " ", false) {
+                    pressed = Some(KeyPress::Text(' '));
+                }
+            }
+            if key(ui, key_size, "◀", false) {
+                pressed = Some(KeyPress::Key(Key::ArrowLeft));
+            }
+            if key(ui, key_size, "▶", false) {
+                pressed = Some(KeyPress::Key(Key::ArrowRight));
+            }
+            if self.layout != KeyboardLayout::Numeric && key(ui, wide(1.5), "⏎", false) {
+                pressed = Some(KeyPress::Key(Key::Enter));
+            }
+        });
+        pressed
+    }
+}
+
+/// Registers (once per context) the callback, injecting the pressed keys into the input at the
+/// beginning of the next frame
+fn register_injection(ctx: &Context) {
+    let registered_id = Id::new(EVENTS_ID).with("registered");
+    if ctx.data(|d| d.get_temp::<bool>(registered_id).unwrap_or_default()) {
+        return;
+    }
+    ctx.data_mut(|d| d.insert_temp(registered_id, true));
+    ctx.on_begin_pass(
+        "ehmi_on_screen_keyboard",
+        Arc::new(|ctx| {
+            let events = ctx.data_mut(|d| d.remove_temp::<Vec<Event>>(Id::new(EVENTS_ID)));
+            if let Some(events) = events {
+                ctx.input_mut(|i| i.events.extend(events));
+            }
+        }),
+    );
+}
@@ -0,0 +1,386 @@
+use core::fmt;
+use std::ops::RangeInclusive;
+
+use egui::{
+    epaint::PathShape, Align2, Color32, FontId, Id, Modifiers, Pos2, Rect, Response, Sense, Shape,
+    Stroke, Ui, Vec2,
+};
+
+use super::gauge::{arc_points, position_from_angle};
+use crate::accessibility::{AccessInfo, AlarmState, Quality};
+use crate::colors::{get_text_color, SUCCESS};
+use crate::format::{NumberFormat, ValueFormatter};
+use crate::numeric::Numeric;
+use crate::touch::{double_tapped, hit_scale};
+
+/// Knob drag mode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KnobDrag {
+    /// Dragging up increases the value
+    #[default]
+    Vertical,
+    /// The knob follows the pointer around its center
+    Circular,
+}
+
+/// Default knob size, scaled with the touch profile
+const DEFAULT_SIZE: f32 = 60.0;
+
+/// Drag distance (in points) or scroll distance for the full range
+const FULL_RANGE_DRAG: f64 = 200.0;
+
+/// Rotary knob, bound to a value
+pub struct Knob<'a> {
+    value: &'a mut f64,
+    value_range: RangeInclusive<f64>,
+    size: Option<f32>,
+    angle_range: RangeInclusive<f32>,
+    drag: KnobDrag,
+    step: Option<f64>,
+    default_value: Option<f64>,
+    fine_modifier: Modifiers,
+    fine_factor: f64,
+    text: Option<String>,
+    show_value: bool,
+    color: Color32,
+    text_color: Option<Color32>,
+    formatter: Option<Box<dyn ValueFormatter>>,
+}
+
+impl<'a> Knob<'a> {
+    /// Create a new knob
+    pub fn new(value: &'a mut f64) -> Self {
+        Self {
+            value,
+            value_range: 0.0..=100.0,
+            size: None,
+            angle_range: -45.0..=225.0,
+            drag: KnobDrag::Vertical,
+            step: None,
+            default_value: None,
+            fine_modifier: Modifiers::SHIFT,
+            fine_factor: 0.1,
+            text: None,
+            show_value: true,
+            color: SUCCESS,
+            text_color: None,
+            formatter: None,
+        }
+    }
+
+    /// Set the value range of the knob
+    pub fn range<V>(mut self, range: RangeInclusive<V>) -> Self
+    where
+        V: Numeric,
+    {
+        self.value_range = range.start().to_f64()..=range.end().to_f64();
+        self
+    }
+
+    /// Set the size of the knob
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the angle range of the knob in degrees (-45..=225 by default)
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn angle_range<V>(mut self, angle_range: RangeInclusive<V>) -> Self
+    where
+        V: Numeric,
+    {
+        let start = (angle_range.start().to_f64() as f32).clamp(-360.0, 360.0);
+        let end = (angle_range.end().to_f64() as f32).clamp(-360.0, 360.0);
+        self.angle_range = start..=end;
+        self
+    }
+
+    /// Set the drag mode (vertical by default)
+    pub fn drag(mut self, drag: KnobDrag) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Set the step (detents) of the value, the steps are counted from the range start
+    pub fn step<V>(mut self, step: V) -> Self
+    where
+        V: Numeric,
+    {
+        let step = step.to_f64();
+        self.step = (step > 0.0).then_some(step);
+        self
+    }
+
+    /// Set the value, restored by double click (the range start by default)
+    pub fn default_value<V>(mut self, value: V) -> Self
+    where
+        V: Numeric,
+    {
+        self.default_value = Some(value.to_f64());
+        self
+    }
+
+    /// Set the modifier key for fine adjustment (shift by default)
+    pub fn fine_modifier(mut self, modifier: Modifiers) -> Self {
+        self.fine_modifier = modifier;
+        self
+    }
+
+    /// Set the speed factor of the fine adjustment (0.1 by default)
+    pub fn fine_factor(mut self, factor: f64) -> Self {
+        self.fine_factor = factor;
+        self
+    }
+
+    /// Set the text, displayed below the knob
+    pub fn text(mut self, text: impl fmt::Display) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Display the value in the knob center (enabled by default)
+    pub fn show_value(mut self, show_value: bool) -> Self {
+        self.show_value = show_value;
+        self
+    }
+
+    /// Set the color of the value arc
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the color of the text
+    pub fn text_color(mut self, color: Color32) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Set the formatter for the displayed value
+    pub fn formatter(mut self, formatter: impl ValueFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    fn min(&self) -> f64 {
+        self.value_range.start().min(*self.value_range.end())
+    }
+
+    fn max(&self) -> f64 {
+        self.value_range.start().max(*self.value_range.end())
+    }
+
+    fn span(&self) -> f64 {
+        self.max() - self.min()
+    }
+
+    fn snap(&self, value: f64) -> f64 {
+        let value = value.clamp(self.min(), self.max());
+        if let Some(step) = self.step {
+            let snapped = self.min() + ((value - self.min()) / step).round() * step;
+            snapped.clamp(self.min(), self.max())
+        } else {
+            value
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn value_to_angle(&self, value: f64) -> f32 {
+        let (min_angle, max_angle) = (*self.angle_range.start(), *self.angle_range.end());
+        let normalized = if self.span() > 0.0 {
+            ((value - self.min()) / self.span()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        max_angle - normalized as f32 * (max_angle - min_angle)
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        if let Some(ref formatter) = self.formatter {
+            formatter.format(value)
+        } else if self.step.is_some_and(|step| step.fract() == 0.0) {
+            NumberFormat::new().precision(0).format(value)
+        } else {
+            NumberFormat::new().format(value)
+        }
+    }
+
+    /// Value change (unsnapped) caused by the drag and scroll input
+    fn input_delta(&self, ui: &mut Ui, response: &Response, dial: Rect) -> f64 {
+        let mut delta = 0.0;
+        if response.dragged() {
+            let drag_delta = response.drag_delta();
+            match self.drag {
+                KnobDrag::Vertical => {
+                    delta -= f64::from(drag_delta.y) / FULL_RANGE_DRAG;
+                }
+                KnobDrag::Circular => {
+                    if let Some(pos) = response.interact_pointer_pos() {
+                        let angle_of = |p: Pos2| {
+                            let v = p - dial.center();
+                            (-v.y).atan2(v.x).to_degrees()
+                        };
+                        let mut angle_delta = angle_of(pos) - angle_of(pos - drag_delta);
+                        if angle_delta > 180.0 {
+                            angle_delta -= 360.0;
+                        } else if angle_delta < -180.0 {
+                            angle_delta += 360.0;
+                        }
+                        let sweep = self.angle_range.end() - self.angle_range.start();
+                        if sweep.abs() > 0.0 {
+                            delta -= f64::from(angle_delta / sweep);
+                        }
+                    }
+                }
+            }
+        }
+        if response.hovered() {
+            // consumed, so the enclosing scroll area does not scroll as well
+            let scroll = ui.input_mut(|i| core::mem::take(&mut i.smooth_scroll_delta.y));
+            delta += f64::from(scroll) / FULL_RANGE_DRAG;
+        }
+        if delta != 0.0 && ui.input(|i| i.modifiers.matches_logically(self.fine_modifier)) {
+            delta *= self.fine_factor;
+        }
+        delta * self.span()
+    }
+
+    fn paint(&self, ui: &mut Ui, response: &Response, dial: Rect) {
+        let center = dial.center();
+        let radius = dial.width() / 2.0;
+        let arc_width = (radius / 6.0).max(2.0);
+        let arc_radius = radius - arc_width / 2.0;
+        let (min_angle, max_angle) = (*self.angle_range.start(), *self.angle_range.end());
+        let angle = self.value_to_angle(*self.value);
+
+        let arc = |ui: &Ui, start: f32, end: f32, color: Color32| {
+            if start >= end {
+                return;
+            }
+            ui.painter().add(Shape::Path(PathShape {
+                points: arc_points(ui, dial, arc_radius, start, end),
+                closed: false,
+                fill: Color32::TRANSPARENT,
+                stroke: Stroke::new(arc_width, color).into(),
+            }));
+        };
+        arc(
+            ui,
+            min_angle.min(max_angle),
+            min_angle.max(max_angle),
+            ui.visuals().extreme_bg_color,
+        );
+        arc(ui, angle.min(max_angle), angle.max(max_angle), self.color);
+
+        let visuals = ui.style().interact(response);
+        let body_radius = radius - arc_width * 1.75;
+        ui.painter()
+            .circle(center, body_radius, visuals.bg_fill, visuals.bg_stroke);
+
+        if let Some(step) = self.step {
+            let steps = self.span() / step;
+            if steps <= 40.0 {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                for i in 0..=steps.floor() as usize {
+                    #[allow(clippy::cast_precision_loss)]
+                    let detent = self.value_to_angle(self.min() + step * i as f64);
+                    ui.painter().circle_filled(
+                        position_from_angle(dial, detent, body_radius + arc_width * 0.75),
+                        1.0,
+                        visuals.fg_stroke.color,
+                    );
+                }
+            }
+        }
+
+        ui.painter().line_segment(
+            [
+                position_from_angle(dial, angle, body_radius * 0.4),
+                position_from_angle(dial, angle, body_radius * 0.9),
+            ],
+            Stroke::new((radius / 15.0).max(1.5), visuals.fg_stroke.color),
+        );
+
+        let text_color = self.text_color.unwrap_or_else(|| get_text_color(ui));
+        if self.show_value {
+            ui.painter().text(
+                center,
+                Align2::CENTER_CENTER,
+                self.format_value(*self.value),
+                FontId::proportional(body_radius * 0.5),
+                text_color,
+            );
+        }
+        if let Some(ref text) = self.text {
+            ui.painter().text(
+                dial.center_bottom(),
+                Align2::CENTER_TOP,
+                text,
+                FontId::proportional(text_height(dial.width())),
+                text_color,
+            );
+        }
+    }
+}
+
+fn text_height(size: f32) -> f32 {
+    (size / 5.0).max(10.0)
+}
+
+impl egui::Widget for Knob<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let size = self
+            .size
+            .unwrap_or_else(|| DEFAULT_SIZE * hit_scale(ui.ctx()));
+        let text_height = if self.text.is_some() {
+            text_height(size)
+        } else {
+            0.0
+        };
+        let (rect, mut response) =
+            ui.allocate_exact_size(Vec2::new(size, size + text_height), Sense::click_and_drag());
+        let dial = Rect::from_min_size(rect.min, Vec2::splat(size));
+
+        // the unsnapped value, kept while the input continues between the steps
+        let raw_id: Id = response.id.with("raw");
+        let current = *self.value;
+        if double_tapped(&response) {
+            *self.value = self.snap(self.default_value.unwrap_or(self.min()));
+            ui.data_mut(|d| d.remove::<f64>(raw_id));
+        } else {
+            let delta = self.input_delta(ui, &response, dial);
+            if delta != 0.0 {
+                let raw = ui
+                    .data(|d| d.get_temp::<f64>(raw_id))
+                    .filter(|raw| (self.snap(*raw) - current).abs() <= f64::EPSILON)
+                    .unwrap_or(current);
+                let raw = (raw + delta).clamp(self.min(), self.max());
+                ui.data_mut(|d| d.insert_temp(raw_id, raw));
+                *self.value = self.snap(raw);
+            }
+        }
+        if (*self.value - current).abs() > 0.0 {
+            response.mark_changed();
+        }
+
+        AccessInfo {
+            typ: egui::WidgetType::Slider,
+            role: "knob",
+            label: self.text.as_ref().map_or("", |s| s.as_str()),
+            value: *self.value,
+            text: self.format_value(*self.value),
+            range: self.min()..=self.max(),
+            step: self.step,
+            unit: None,
+            alarm: AlarmState::Normal,
+            quality: Quality::Good,
+        }
+        .report(ui, &response);
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, &response, dial);
+        }
+
+        response
+    }
+}
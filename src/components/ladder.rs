@@ -0,0 +1,592 @@
+use core::fmt;
+
+use egui::{
+    pos2, vec2, Align2, Color32, FontId, Painter, Pos2, Rect, Response, Sense, Stroke, StrokeKind,
+    Ui, Vec2, WidgetInfo, WidgetType,
+};
+
+use crate::colors::{get_text_color, GRAY, SUCCESS};
+
+/// Kind of the ladder logic output coil
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoilKind {
+    /// Energized while the rung is energized, -( )-
+    #[default]
+    Normal,
+    /// Energized while the rung is not energized, -(/)-
+    Negated,
+    /// Latched on by the energized rung, -(S)-. The latched bit, set with
+    /// [`LadderRung::coil_state`], is displayed
+    Set,
+    /// Latched off by the energized rung, -(R)-. The latched bit, set with
+    /// [`LadderRung::coil_state`], is displayed
+    Reset,
+}
+
+/// Kind of the ladder logic timer block
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerKind {
+    /// On-delay timer (TON)
+    OnDelay,
+    /// Off-delay timer (TOF)
+    OffDelay,
+}
+
+impl TimerKind {
+    fn name(self) -> &'static str {
+        match self {
+            TimerKind::OnDelay => "TON",
+            TimerKind::OffDelay => "TOF",
+        }
+    }
+}
+
+enum Element {
+    Contact {
+        label: String,
+        on: bool,
+        normally_closed: bool,
+    },
+    Timer {
+        label: String,
+        kind: TimerKind,
+        output: bool,
+        progress: f32,
+    },
+    Parallel(Vec<Vec<Element>>),
+}
+
+impl Element {
+    fn size(&self, cell: Vec2) -> Vec2 {
+        match self {
+            Element::Contact { .. } | Element::Timer { .. } => cell,
+            Element::Parallel(branches) => branches
+                .iter()
+                .map(|branch| series_size(branch, cell))
+                .fold(Vec2::ZERO, |acc, size| {
+                    vec2(acc.x.max(size.x), acc.y + size.y)
+                }),
+        }
+    }
+}
+
+fn series_size(elements: &[Element], cell: Vec2) -> Vec2 {
+    elements
+        .iter()
+        .map(|element| element.size(cell))
+        .fold(vec2(0.0, cell.y), |acc, size| {
+            vec2(acc.x + size.x, acc.y.max(size.y))
+        })
+}
+
+struct Coil {
+    label: String,
+    kind: CoilKind,
+}
+
+/// Ladder logic rung with live power flow: contacts, timers and parallel branches are evaluated
+/// from the left (power) rail to the output coil, energized paths are highlighted
+pub struct LadderRung {
+    elements: Vec<Element>,
+    coil: Option<Coil>,
+    coil_state: Option<bool>,
+    cell_size: Vec2,
+    width: Option<f32>,
+    text: Option<String>,
+    energized_color: Color32,
+}
+
+impl Default for LadderRung {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LadderRung {
+    /// Create a new empty rung
+    pub fn new() -> Self {
+        Self {
+            elements: Vec::new(),
+            coil: None,
+            coil_state: None,
+            cell_size: vec2(60.0, 44.0),
+            width: None,
+            text: None,
+            energized_color: SUCCESS,
+        }
+    }
+
+    /// Add a normally-open contact, -| |-, closed while the input is on
+    pub fn contact(mut self, label: impl fmt::Display, on: bool) -> Self {
+        self.elements.push(Element::Contact {
+            label: label.to_string(),
+            on,
+            normally_closed: false,
+        });
+        self
+    }
+
+    /// Add a normally-closed contact, -|/|-, closed while the input is off
+    pub fn nc_contact(mut self, label: impl fmt::Display, on: bool) -> Self {
+        self.elements.push(Element::Contact {
+            label: label.to_string(),
+            on,
+            normally_closed: true,
+        });
+        self
+    }
+
+    /// Add a timer block. The timer output (Q) passes the power, the progress (elapsed time /
+    /// preset time, 0.0-1.0) is displayed as a bar inside the block
+    pub fn timer(
+        mut self,
+        label: impl fmt::Display,
+        kind: TimerKind,
+        output: bool,
+        progress: f32,
+    ) -> Self {
+        self.elements.push(Element::Timer {
+            label: label.to_string(),
+            kind,
+            output,
+            progress: progress.clamp(0.0, 1.0),
+        });
+        self
+    }
+
+    /// Add parallel (OR) branches. Each branch is a rung, its coil is ignored
+    pub fn parallel(mut self, branches: impl IntoIterator<Item = LadderRung>) -> Self {
+        self.elements.push(Element::Parallel(
+            branches.into_iter().map(|branch| branch.elements).collect(),
+        ));
+        self
+    }
+
+    /// Set the output coil, placed next to the right rail
+    pub fn coil(mut self, label: impl fmt::Display, kind: CoilKind) -> Self {
+        self.coil = Some(Coil {
+            label: label.to_string(),
+            kind,
+        });
+        self
+    }
+
+    /// Set the size of a single element cell (the label is included in the height)
+    pub fn cell_size(mut self, size: Vec2) -> Self {
+        self.cell_size = size;
+        self
+    }
+
+    /// Set the actual state of the coil bit, displayed instead of the rung power. Required for
+    /// the latching (Set/Reset) coils, which keep the state when the rung is not energized,
+    /// these are displayed as off otherwise
+    pub fn coil_state(mut self, on: bool) -> Self {
+        self.coil_state = Some(on);
+        self
+    }
+
+    /// Set the minimum width of the rung, useful to align coils of several rungs
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set the text (rung comment), displayed above the rung
+    pub fn text(mut self, text: impl fmt::Display) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Set the color of the energized paths
+    pub fn energized_color(mut self, color: Color32) -> Self {
+        self.energized_color = color;
+        self
+    }
+
+    fn font_size(&self) -> f32 {
+        (self.cell_size.y / 4.0).max(9.0)
+    }
+}
+
+const RAIL_WIDTH: f32 = 3.0;
+
+impl egui::Widget for LadderRung {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let cell = self.cell_size;
+        let font = FontId::proportional(self.font_size());
+        let text_height = if self.text.is_some() {
+            font.size + 4.0
+        } else {
+            0.0
+        };
+        let content = series_size(&self.elements, cell);
+        let coil_width = if self.coil.is_some() { cell.x } else { 0.0 };
+        let width = (content.x + coil_width + 2.0 * RAIL_WIDTH).max(self.width.unwrap_or(0.0));
+        let (rect, response) =
+            ui.allocate_exact_size(vec2(width, text_height + content.y), Sense::hover());
+
+        let rung = RungPainter {
+            painter: ui.painter(),
+            cell,
+            font: font.clone(),
+            text_color: get_text_color(ui),
+            energized: Stroke::new(2.0, self.energized_color),
+            de_energized: Stroke::new(1.0, GRAY),
+        };
+        let rung_rect = Rect::from_min_max(pos2(rect.left(), rect.top() + text_height), rect.max);
+        let content_min = pos2(rung_rect.left() + RAIL_WIDTH, rung_rect.top());
+        let series_width = rung_rect.width() - coil_width - 2.0 * RAIL_WIDTH;
+        // the power is evaluated and painted in one pass, hidden rungs are evaluated for the
+        // accessibility state only
+        let visible = ui.is_rect_visible(rect);
+        let power = rung.series(&self.elements, content_min, series_width, true, visible);
+        let coil_on = self.coil.as_ref().map(|coil| {
+            self.coil_state.unwrap_or(match coil.kind {
+                CoilKind::Normal => power,
+                CoilKind::Negated => !power,
+                CoilKind::Set | CoilKind::Reset => false,
+            })
+        });
+
+        if visible {
+            if let Some(ref text) = self.text {
+                rung.painter.text(
+                    rect.left_top(),
+                    Align2::LEFT_TOP,
+                    text,
+                    font.clone(),
+                    rung.text_color,
+                );
+            }
+            rung.painter.line_segment(
+                [
+                    pos2(rung_rect.left() + 1.0, rung_rect.top()),
+                    pos2(rung_rect.left() + 1.0, rung_rect.bottom()),
+                ],
+                Stroke::new(RAIL_WIDTH - 1.0, self.energized_color),
+            );
+            rung.painter.line_segment(
+                [
+                    pos2(rung_rect.right() - 1.0, rung_rect.top()),
+                    pos2(rung_rect.right() - 1.0, rung_rect.bottom()),
+                ],
+                Stroke::new(RAIL_WIDTH - 1.0, GRAY),
+            );
+            if let (Some(coil), Some(on)) = (&self.coil, coil_on) {
+                let coil_rect = Rect::from_min_size(
+                    pos2(rung_rect.right() - RAIL_WIDTH - cell.x, rung_rect.top()),
+                    cell,
+                );
+                rung.coil(coil, coil_rect, power, on);
+            }
+        }
+
+        response.widget_info(|| {
+            let state = match (&self.coil, coil_on) {
+                (Some(coil), Some(on)) => format!(
+                    "{}: {}",
+                    coil.label,
+                    if on { "energized" } else { "de-energized" }
+                ),
+                _ if power => "energized".to_owned(),
+                _ => "de-energized".to_owned(),
+            };
+            let label = match self.text {
+                Some(ref text) => format!("{text}, {state}"),
+                None => state,
+            };
+            WidgetInfo::labeled(WidgetType::Label, ui.is_enabled(), label)
+        });
+        response
+    }
+}
+
+struct RungPainter<'a> {
+    painter: &'a Painter,
+    cell: Vec2,
+    font: FontId,
+    text_color: Color32,
+    energized: Stroke,
+    de_energized: Stroke,
+}
+
+impl RungPainter<'_> {
+    fn stroke(&self, power: bool) -> Stroke {
+        if power {
+            self.energized
+        } else {
+            self.de_energized
+        }
+    }
+
+    fn wire_y(&self, top: f32) -> f32 {
+        top + self.cell.y * 0.65
+    }
+
+    fn label(&self, rect: Rect, label: &str) {
+        self.painter.text(
+            pos2(rect.center().x, rect.top()),
+            Align2::CENTER_TOP,
+            label,
+            self.font.clone(),
+            self.text_color,
+        );
+    }
+
+    /// Evaluates (and paints if visible) the elements in series, the wire is extended up to the
+    /// given width. Returns the output power
+    fn series(
+        &self,
+        elements: &[Element],
+        min: Pos2,
+        width: f32,
+        mut power: bool,
+        visible: bool,
+    ) -> bool {
+        let mut x = min.x;
+        for element in elements {
+            let size = element.size(self.cell);
+            let rect = Rect::from_min_size(pos2(x, min.y), size);
+            power = self.element(element, rect, power, visible);
+            x += size.x;
+        }
+        if visible && x < min.x + width {
+            let y = self.wire_y(min.y);
+            self.painter
+                .line_segment([pos2(x, y), pos2(min.x + width, y)], self.stroke(power));
+        }
+        power
+    }
+
+    fn element(&self, element: &Element, rect: Rect, power: bool, visible: bool) -> bool {
+        match element {
+            Element::Contact {
+                label,
+                on,
+                normally_closed,
+            } => {
+                let closed = on != normally_closed;
+                let output = power && closed;
+                if visible {
+                    self.label(rect, label);
+                    let symbol_rect = Rect::from_center_size(
+                        pos2(rect.center().x, self.wire_y(rect.top())),
+                        vec2(rect.width(), self.cell.y * 0.4),
+                    );
+                    paint_contact(
+                        self.painter,
+                        symbol_rect,
+                        if closed { 1.0 } else { 0.0 },
+                        *normally_closed,
+                        self.stroke(power),
+                        self.stroke(output),
+                    );
+                }
+                output
+            }
+            Element::Timer {
+                label,
+                kind,
+                output,
+                progress,
+            } => {
+                if visible {
+                    self.label(rect, label);
+                    let y = self.wire_y(rect.top());
+                    let block = Rect::from_center_size(
+                        pos2(rect.center().x, y),
+                        vec2(rect.width() * 0.6, self.cell.y * 0.5),
+                    );
+                    self.painter.line_segment(
+                        [pos2(rect.left(), y), pos2(block.left(), y)],
+                        self.stroke(power),
+                    );
+                    self.painter.line_segment(
+                        [pos2(block.right(), y), pos2(rect.right(), y)],
+                        self.stroke(*output),
+                    );
+                    if *progress > 0.0 {
+                        let mut bar = block.shrink(2.0);
+                        bar.set_top(bar.bottom() - 3.0);
+                        bar.set_right(egui::lerp(bar.left()..=bar.right(), *progress));
+                        self.painter.rect_filled(bar, 0.0, self.energized.color);
+                    }
+                    self.painter
+                        .rect_stroke(block, 2.0, self.stroke(*output), StrokeKind::Inside);
+                    self.painter.text(
+                        pos2(block.center().x, block.center().y - 1.0),
+                        Align2::CENTER_CENTER,
+                        kind.name(),
+                        FontId::proportional(self.font.size * 0.8),
+                        self.text_color,
+                    );
+                }
+                *output
+            }
+            Element::Parallel(branches) => self.parallel(branches, rect, power, visible),
+        }
+    }
+
+    /// Evaluates (and paints if visible) the parallel branches
+    fn parallel(&self, branches: &[Vec<Element>], rect: Rect, power: bool, visible: bool) -> bool {
+        // evaluated first, the whole right joint is colored by the output
+        let output = branches
+            .iter()
+            .any(|branch| self.series(branch, rect.min, 0.0, power, false));
+        if !visible {
+            return output;
+        }
+        let mut y = rect.top();
+        let (mut first, mut last) = (None, None);
+        for branch in branches {
+            let wire_y = self.wire_y(y);
+            self.series(branch, pos2(rect.left(), y), 0.0, power, true);
+            // the branch ends and the right joint are the same node, energized if any
+            // of the branches is
+            let size = series_size(branch, self.cell);
+            if size.x < rect.width() {
+                self.painter.line_segment(
+                    [
+                        pos2(rect.left() + size.x, wire_y),
+                        pos2(rect.right(), wire_y),
+                    ],
+                    self.stroke(output),
+                );
+            }
+            first.get_or_insert(wire_y);
+            last = Some(wire_y);
+            y += size.y;
+        }
+        if let (Some(first), Some(last)) = (first, last) {
+            self.painter.line_segment(
+                [pos2(rect.left(), first), pos2(rect.left(), last)],
+                self.stroke(power),
+            );
+            self.painter.line_segment(
+                [pos2(rect.right(), first), pos2(rect.right(), last)],
+                self.stroke(output),
+            );
+        }
+        output
+    }
+
+    fn coil(&self, coil: &Coil, rect: Rect, power: bool, on: bool) {
+        self.label(rect, &coil.label);
+        let center = pos2(rect.center().x, self.wire_y(rect.top()));
+        let radius = self.cell.y * 0.2;
+        let stroke = self.stroke(on);
+        self.painter.line_segment(
+            [
+                pos2(rect.left(), center.y),
+                pos2(center.x - radius, center.y),
+            ],
+            self.stroke(power),
+        );
+        self.painter.line_segment(
+            [
+                pos2(center.x + radius, center.y),
+                pos2(rect.right(), center.y),
+            ],
+            self.de_energized,
+        );
+        if on {
+            self.painter.circle_filled(
+                center,
+                radius * 0.8,
+                self.energized.color.gamma_multiply(0.4),
+            );
+        }
+        // the brackets are arcs of a larger circle, shifted to the opposite side
+        let arc_radius = radius * 1.6;
+        let shift = arc_radius - radius;
+        for (arc_center, base) in [
+            (center + vec2(shift, 0.0), 180.0_f32),
+            (center - vec2(shift, 0.0), 0.0_f32),
+        ] {
+            let points = (0..=8_u8)
+                .map(|i| {
+                    let angle = (base - 40.0 + 10.0 * f32::from(i)).to_radians();
+                    arc_center + arc_radius * vec2(angle.cos(), -angle.sin())
+                })
+                .collect();
+            self.painter.add(egui::Shape::line(points, stroke));
+        }
+        let font = FontId::proportional(self.font.size * 0.8);
+        match coil.kind {
+            CoilKind::Normal => {}
+            CoilKind::Negated => {
+                self.painter.line_segment(
+                    [
+                        center + vec2(-radius, radius) * 0.5,
+                        center + vec2(radius, -radius) * 0.5,
+                    ],
+                    stroke,
+                );
+            }
+            CoilKind::Set | CoilKind::Reset => {
+                let letter = if coil.kind == CoilKind::Set { "S" } else { "R" };
+                self.painter
+                    .text(center, Align2::CENTER_CENTER, letter, font, self.text_color);
+            }
+        }
+    }
+}
+
+/// Paints a relay contact across the rect: terminal nodes at 1/3 and 2/3 of the width and the
+/// blade pivoting on the right node (0.0 - open at 45 degrees, 1.0 - closed). Normally-closed
+/// contacts get a stop mark at the left node. The input stroke is used for the left lead, the
+/// output stroke for the blade and the right lead
+pub(crate) fn paint_contact(
+    painter: &Painter,
+    rect: Rect,
+    closed: f32,
+    normally_closed: bool,
+    input: Stroke,
+    output: Stroke,
+) {
+    let center_y = rect.center().y;
+    let circle_radius = rect.width() * 0.04;
+    let node_left = rect.left() + rect.width() / 3.0;
+    let node_right = rect.right() - rect.width() / 3.0;
+
+    painter.line_segment(
+        [
+            pos2(rect.left(), center_y),
+            pos2(node_left - circle_radius * 2.0, center_y),
+        ],
+        input,
+    );
+
+    let circle_left = pos2(node_left - circle_radius, center_y);
+    let circle_right = pos2(node_right + circle_radius, center_y);
+
+    painter.circle_stroke(circle_left, circle_radius, input);
+    painter.circle_stroke(circle_right, circle_radius, output);
+
+    painter.line_segment(
+        [
+            pos2(rect.right(), center_y),
+            pos2(node_right + circle_radius * 2.0, center_y),
+        ],
+        output,
+    );
+
+    let switch_left = pos2(node_left, center_y);
+    let switch_right = pos2(node_right, center_y);
+    let off_angle = 45.0_f32.to_radians();
+    let angle = egui::lerp(off_angle..=0.0, closed);
+    let length = (switch_right - switch_left).length();
+    let dir = vec2(angle.cos(), angle.sin());
+    let animated_left = switch_right - dir * length;
+    painter.line_segment([animated_left, switch_right], output);
+
+    if normally_closed {
+        painter.line_segment(
+            [
+                pos2(node_left, center_y - circle_radius),
+                pos2(node_left, center_y - circle_radius * 4.0),
+            ],
+            input,
+        );
+    }
+}
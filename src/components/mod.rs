@@ -1,7 +1,19 @@
 mod bar;
+mod compass;
+mod control_valve;
 mod gauge;
+mod keyboard;
+mod knob;
+mod ladder;
+mod setpoint;
 mod toggle_switch;
 
 pub use bar::Bar;
-pub use gauge::Gauge;
-pub use toggle_switch::{ToggleStyle, ToggleSwitch};
+pub use compass::{Compass, CompassMode};
+pub use control_valve::{ControlValve, FailAction, ValveMode};
+pub use gauge::{ArcCap, Gauge, GaugeStyle, GaugeText, NeedleShape, TickLabelOrientation};
+pub use keyboard::{KeyboardLayout, OnScreenKeyboard};
+pub use knob::{Knob, KnobDrag};
+pub use ladder::{CoilKind, LadderRung, TimerKind};
+pub use setpoint::SetpointEntry;
+pub use toggle_switch::{LabelPosition, ToggleStyle, ToggleSwitch, ValveState};
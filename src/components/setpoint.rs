@@ -0,0 +1,422 @@
+use core::fmt;
+use std::ops::RangeInclusive;
+
+use egui::{
+    vec2, Align2, Area, Button, Color32, FontId, Frame, Id, Key, LayerId, Modifiers, Order,
+    Response, RichText, Sense, Stroke, StrokeKind, Ui, Vec2,
+};
+
+use crate::accessibility::{AccessInfo, AlarmState, Quality};
+use crate::colors::SUCCESS;
+use crate::format::{NumberFormat, ValueFormatter};
+use crate::numeric::Numeric;
+use crate::touch::{hit_scale, tapped};
+use crate::units::Unit;
+
+/// Keypad state, stored in egui memory while the keypad is open
+#[derive(Clone, Debug)]
+struct KeypadState {
+    input: String,
+    previous: f64,
+    error: Option<&'static str>,
+}
+
+/// Numeric setpoint entry. Displays the value like a panel meter and opens a numeric keypad on
+/// click. The value is validated and written only when confirmed with OK, the response is
+/// `changed()` only then
+pub struct SetpointEntry<'a> {
+    value: &'a mut f64,
+    value_range: RangeInclusive<f64>,
+    step: Option<f64>,
+    width: f32,
+    key_size: Option<f32>,
+    text: Option<String>,
+    color: Color32,
+    unit: Option<Unit>,
+    formatter: Option<Box<dyn ValueFormatter>>,
+}
+
+impl<'a> SetpointEntry<'a> {
+    /// Create a new setpoint entry
+    pub fn new(value: &'a mut f64) -> Self {
+        Self {
+            value,
+            value_range: f64::NEG_INFINITY..=f64::INFINITY,
+            step: None,
+            width: 120.0,
+            key_size: None,
+            text: None,
+            color: SUCCESS,
+            unit: None,
+            formatter: None,
+        }
+    }
+
+    /// Set the allowed value range (unlimited by default)
+    pub fn range<V>(mut self, range: RangeInclusive<V>) -> Self
+    where
+        V: Numeric,
+    {
+        self.value_range = range.start().to_f64()..=range.end().to_f64();
+        self
+    }
+
+    /// Set the step, the entered value must be a multiple of the step counted from the range
+    /// start (or from zero if the range is unlimited)
+    pub fn step<V>(mut self, step: V) -> Self
+    where
+        V: Numeric,
+    {
+        let step = step.to_f64();
+        self.step = (step > 0.0).then_some(step);
+        self
+    }
+
+    /// Set the width of the display
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the size of the keypad keys (40 points scaled with the touch profile by default)
+    pub fn key_size(mut self, size: f32) -> Self {
+        self.key_size = Some(size);
+        self
+    }
+
+    /// Set the text, displayed as the keypad title
+    pub fn text(mut self, text: impl fmt::Display) -> Self {
+        self.text = Some(text.to_string());
+        self
+    }
+
+    /// Set the color of the displayed value
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the engineering unit, the unit symbol is displayed next to the value
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Set the formatter for the displayed value
+    pub fn formatter(mut self, formatter: impl ValueFormatter + 'static) -> Self {
+        self.formatter = Some(Box::new(formatter));
+        self
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        if let Some(ref formatter) = self.formatter {
+            formatter.format(value)
+        } else {
+            NumberFormat::new().format(value)
+        }
+    }
+
+    fn validate(&self, input: &str) -> Result<f64, &'static str> {
+        let value: f64 = input.trim().parse().map_err(|_| "Invalid number")?;
+        if !value.is_finite() {
+            return Err("Invalid number");
+        }
+        if value < *self.value_range.start() {
+            return Err("Below the minimum");
+        }
+        if value > *self.value_range.end() {
+            return Err("Above the maximum");
+        }
+        if let Some(step) = self.step {
+            let base = if self.value_range.start().is_finite() {
+                *self.value_range.start()
+            } else {
+                0.0
+            };
+            let steps = (value - base) / step;
+            if (steps - steps.round()).abs() > 1e-9 * steps.abs().max(1.0) {
+                return Err("Not a multiple of the step");
+            }
+        }
+        Ok(value)
+    }
+
+    fn limits_text(&self) -> Option<String> {
+        let (min, max) = (*self.value_range.start(), *self.value_range.end());
+        let limit = |v: f64| {
+            if v.is_finite() {
+                NumberFormat::new().format(v)
+            } else {
+                String::new()
+            }
+        };
+        let mut parts = Vec::new();
+        if min.is_finite() || max.is_finite() {
+            parts.push(format!("{} .. {}", limit(min), limit(max)));
+        }
+        if let Some(step) = self.step {
+            parts.push(format!("step {}", NumberFormat::new().format(step)));
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    fn paint_display(&self, ui: &Ui, response: &Response, open: bool) {
+        let rect = response.rect;
+        let visuals = ui.style().interact(response);
+        let stroke = if open {
+            Stroke::new(1.5, self.color)
+        } else {
+            visuals.bg_stroke
+        };
+        let painter = ui.painter();
+        painter.rect(
+            rect,
+            visuals.corner_radius,
+            ui.visuals().extreme_bg_color,
+            stroke,
+            StrokeKind::Inside,
+        );
+        let margin = ui.spacing().button_padding.x;
+        let mut right = rect.right() - margin;
+        if let Some(unit) = self.unit {
+            let unit_rect = painter.text(
+                egui::pos2(right, rect.center().y),
+                Align2::RIGHT_CENTER,
+                unit.symbol(),
+                FontId::proportional(rect.height() * 0.4),
+                ui.visuals().text_color(),
+            );
+            right = unit_rect.left() - margin / 2.0;
+        }
+        painter.text(
+            egui::pos2(right, rect.center().y),
+            Align2::RIGHT_CENTER,
+            self.format_value(*self.value),
+            FontId::monospace(rect.height() * 0.6),
+            self.color,
+        );
+    }
+
+    /// Applies a keypad key to the input, returns `Some(true)` on OK and `Some(false)` on
+    /// cancel
+    fn apply_key(state: &mut KeypadState, key: &str) -> Option<bool> {
+        match key {
+            "OK" => return Some(true),
+            "Cancel" => return Some(false),
+            "⌫" => {
+                state.input.pop();
+            }
+            "C" => state.input.clear(),
+            "±" => {
+                if let Some(stripped) = state.input.strip_prefix('-') {
+                    state.input = stripped.to_owned();
+                } else {
+                    state.input.insert(0, '-');
+                }
+            }
+            "." => {
+                if !state.input.contains('.') {
+                    state.input.push('.');
+                }
+            }
+            digit => state.input.push_str(digit),
+        }
+        state.error = None;
+        None
+    }
+
+    /// Shows the keypad, returns the pressed key
+    fn keypad_ui(&self, ui: &mut Ui, state: &KeypadState, key_size: f32) -> Option<&'static str> {
+        const KEYS: [[&str; 4]; 4] = [
+            ["7", "8", "9", "⌫"],
+            ["4", "5", "6", "C"],
+            ["1", "2", "3", "±"],
+            ["0", ".", "", ""],
+        ];
+        let font = FontId::proportional(key_size * 0.45);
+        let key_size = Vec2::splat(key_size);
+        let mut pressed = None;
+        if let Some(ref text) = self.text {
+            ui.strong(text);
+        }
+        ui.label(format!("Previous: {}", self.format_value(state.previous)));
+        if let Some(limits) = self.limits_text() {
+            ui.label(limits);
+        }
+        let (input_rect, _) = ui.allocate_exact_size(
+            vec2(
+                key_size.x * 4.0 + ui.spacing().item_spacing.x * 3.0,
+                key_size.y,
+            ),
+            Sense::hover(),
+        );
+        ui.painter().rect(
+            input_rect,
+            ui.visuals().widgets.noninteractive.corner_radius,
+            ui.visuals().extreme_bg_color,
+            ui.visuals().widgets.noninteractive.bg_stroke,
+            StrokeKind::Inside,
+        );
+        ui.painter().text(
+            input_rect.right_center() - vec2(ui.spacing().button_padding.x, 0.0),
+            Align2::RIGHT_CENTER,
+            &state.input,
+            FontId::monospace(key_size.y * 0.5),
+            self.color,
+        );
+        if let Some(error) = state.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        for row in KEYS {
+            ui.horizontal(|ui| {
+                for key in row {
+                    if key.is_empty() {
+                        ui.allocate_exact_size(key_size, Sense::hover());
+                    } else if ui
+                        .add_sized(key_size, Button::new(RichText::new(key).font(font.clone())))
+                        .clicked()
+                    {
+                        pressed = Some(key);
+                    }
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            let button_size = vec2(key_size.x * 2.0 + ui.spacing().item_spacing.x, key_size.y);
+            for key in ["Cancel", "OK"] {
+                if ui
+                    .add_sized(
+                        button_size,
+                        Button::new(RichText::new(key).font(font.clone())),
+                    )
+                    .clicked()
+                {
+                    pressed = Some(key);
+                }
+            }
+        });
+        pressed
+    }
+
+    /// Physical keyboard input while the keypad is open. The handled events are consumed, so
+    /// other widgets do not act on them. Enter is handled only if `confirm` is set, otherwise
+    /// it is left to the focused keypad button
+    fn keyboard_input(ui: &mut Ui, state: &mut KeypadState, confirm: bool) -> Option<bool> {
+        ui.input_mut(|i| {
+            i.events.retain(|event| {
+                let egui::Event::Text(text) = event else {
+                    return true;
+                };
+                let mut handled = false;
+                for c in text.chars() {
+                    let key = match c {
+                        '0'..='9' => c.to_string(),
+                        '.' | ',' => ".".to_owned(),
+                        '-' => "±".to_owned(),
+                        _ => continue,
+                    };
+                    Self::apply_key(state, &key);
+                    handled = true;
+                }
+                !handled
+            });
+            for _ in 0..i.count_and_consume_key(Modifiers::NONE, Key::Backspace) {
+                Self::apply_key(state, "⌫");
+            }
+            if confirm && i.consume_key(Modifiers::NONE, Key::Enter) {
+                Some(true)
+            } else if i.consume_key(Modifiers::NONE, Key::Escape) {
+                Some(false)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl egui::Widget for SetpointEntry<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let scale = hit_scale(ui.ctx());
+        let height = ui.spacing().interact_size.y * 1.6 * scale;
+        let key_size = self.key_size.unwrap_or(40.0 * scale);
+        let (rect, mut response) = ui.allocate_exact_size(vec2(self.width, height), Sense::click());
+        let keypad_id: Id = response.id.with("keypad");
+
+        let mut state = ui.data(|d| d.get_temp::<KeypadState>(keypad_id));
+        // the key, which opened the keypad (Enter or Space on the focused entry), is not input
+        let mut opened = false;
+        if tapped(&response) && state.is_none() {
+            state = Some(KeypadState {
+                input: String::new(),
+                previous: *self.value,
+                error: None,
+            });
+            opened = true;
+            response.request_focus();
+        }
+
+        if let Some(mut keypad) = state {
+            let keypad_layer = LayerId::new(Order::Foreground, keypad_id);
+            let keypad_focused = ui
+                .ctx()
+                .memory(egui::Memory::focused)
+                .and_then(|id| ui.ctx().read_response(id))
+                .is_some_and(|r| r.layer_id == keypad_layer);
+            let mut result = if !opened && (response.has_focus() || keypad_focused) {
+                Self::keyboard_input(ui, &mut keypad, response.has_focus())
+            } else {
+                None
+            };
+            let area = Area::new(keypad_id)
+                .order(Order::Foreground)
+                .fixed_pos(rect.left_bottom() + vec2(0.0, ui.spacing().item_spacing.y))
+                .constrain(true)
+                .show(ui.ctx(), |ui| {
+                    Frame::popup(ui.style())
+                        .show(ui, |ui| self.keypad_ui(ui, &keypad, key_size))
+                        .inner
+                });
+            if let Some(key) = area.inner {
+                result = result.or_else(|| Self::apply_key(&mut keypad, key));
+                // pressing the keypad buttons takes the focus, keep typing into the entry
+                response.request_focus();
+            }
+            match result {
+                Some(true) => match self.validate(&keypad.input) {
+                    Ok(value) => {
+                        *self.value = value;
+                        response.mark_changed();
+                        ui.data_mut(|d| d.remove::<KeypadState>(keypad_id));
+                    }
+                    Err(error) => {
+                        keypad.error = Some(error);
+                        ui.data_mut(|d| d.insert_temp(keypad_id, keypad));
+                    }
+                },
+                Some(false) => ui.data_mut(|d| d.remove::<KeypadState>(keypad_id)),
+                None => ui.data_mut(|d| d.insert_temp(keypad_id, keypad)),
+            }
+        }
+
+        AccessInfo {
+            typ: egui::WidgetType::Button,
+            role: "setpoint",
+            label: self.text.as_ref().map_or("", |s| s.as_str()),
+            value: *self.value,
+            text: self.format_value(*self.value),
+            range: self.value_range.clone(),
+            step: self.step,
+            unit: self.unit,
+            alarm: AlarmState::Normal,
+            quality: Quality::Good,
+        }
+        .report(ui, &response);
+
+        if ui.is_rect_visible(rect) {
+            let open = ui.data(|d| d.get_temp::<KeypadState>(keypad_id).is_some());
+            self.paint_display(ui, &response, open);
+        }
+
+        response
+    }
+}
@@ -1,21 +1,78 @@
 use core::fmt;
+use std::time::Duration;
 
-use egui::{pos2, vec2, Align2, Color32, FontId, Stroke, StrokeKind, Ui, Vec2};
+use egui::{pos2, vec2, Align2, Color32, Context, FontId, Id, Stroke, StrokeKind, Ui, Vec2};
 
-use crate::colors::{get_text_color, GRAY, SUCCESS, WARN};
+use super::ladder::paint_contact;
+use crate::colors::{get_text_color, FAULT, GRAY, GRAY_DARK, SUCCESS, WARN};
+use crate::touch::{hit_scale, tapped};
 
 /// Toggle switch style
 #[derive(Clone, Copy, Debug)]
 pub enum ToggleStyle {
     /// Slider button
     Button,
-    /// Relay switch
+    /// Relay switch, normally-open contact
     Relay,
+    /// Relay switch, normally-closed contact
+    RelayNc,
     /// Valve switch
     Valve,
 }
 
-/// Toggle switch component
+/// State of the valve style toggle switch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValveState {
+    /// Open (the open limit switch is on)
+    Open,
+    /// Closed (the closed limit switch is on)
+    Closed,
+    /// Commanded open, traveling
+    Opening,
+    /// Commanded closed, traveling
+    Closing,
+    /// Both limit switches are on, the travel time is exceeded or an external fault is set
+    Fault,
+}
+
+impl ValveState {
+    #[cfg_attr(not(feature = "accesskit"), allow(dead_code))]
+    fn description(self) -> &'static str {
+        match self {
+            ValveState::Open => "open",
+            ValveState::Closed => "closed",
+            ValveState::Opening => "opening",
+            ValveState::Closing => "closing",
+            ValveState::Fault => "fault",
+        }
+    }
+}
+
+/// Last valve command and its time, stored in egui memory
+#[derive(Clone, Copy, Debug)]
+struct ValveCommand {
+    on: bool,
+    since: f64,
+}
+
+/// Label position of the toggle switch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LabelPosition {
+    /// Left of the switch
+    Left,
+    /// Right of the switch
+    #[default]
+    Right,
+    /// Above the switch
+    Top,
+    /// Below the switch
+    Bottom,
+    /// Inside the switch (in the track of the button, at the bottom of the relay and valve
+    /// frames)
+    Inside,
+}
+
+/// Toggle switch component. Focusable with the keyboard, toggled by click, Space or Enter
 pub struct ToggleSwitch<'a> {
     on: &'a mut bool,
     label: Option<String>,
@@ -23,6 +80,11 @@ pub struct ToggleSwitch<'a> {
     style: ToggleStyle,
     size: Option<Vec2>,
     font_size: f32,
+    label_position: LabelPosition,
+    state_text: Option<(String, String)>,
+    travel_time: Duration,
+    limit_switches: Option<(bool, bool)>,
+    fault: bool,
 }
 
 impl<'a> ToggleSwitch<'a> {
@@ -35,6 +97,11 @@ impl<'a> ToggleSwitch<'a> {
             style: ToggleStyle::Button,
             size: None,
             font_size: 14.0,
+            label_position: LabelPosition::Right,
+            state_text: None,
+            travel_time: Duration::from_secs(3),
+            limit_switches: None,
+            fault: false,
         }
     }
 
@@ -67,39 +134,182 @@ impl<'a> ToggleSwitch<'a> {
         self.font_size = size;
         self
     }
+
+    /// Set the label position (right by default)
+    pub fn label_position(mut self, position: LabelPosition) -> Self {
+        self.label_position = position;
+        self
+    }
+
+    /// Set the state text (e.g. "OPEN"/"CLOSED", "I"/"O"). The button style displays it in the
+    /// track, other styles next to the label
+    pub fn state_text(mut self, on: impl fmt::Display, off: impl fmt::Display) -> Self {
+        self.state_text = Some((on.to_string(), off.to_string()));
+        self
+    }
+
+    /// Set the valve travel time (3 seconds by default). Without limit switches, the valve is
+    /// displayed as traveling for this time after the command. With limit switches, the valve
+    /// not reaching the end position in time is displayed as faulty
+    pub fn travel_time(mut self, travel_time: Duration) -> Self {
+        self.travel_time = travel_time;
+        self
+    }
+
+    /// Set the limit switch feedback of the valve style, the displayed state follows the
+    /// feedback instead of the travel timer
+    pub fn limit_switches(mut self, open: bool, closed: bool) -> Self {
+        self.limit_switches = Some((open, closed));
+        self
+    }
+
+    /// Set the external fault of the valve style (e.g. a drive failure)
+    pub fn fault(mut self, fault: bool) -> Self {
+        self.fault = fault;
+        self
+    }
+
+    /// Current valve state, the command time is kept in egui memory
+    /// State of the valve style switch, as of its last frame. The id is the response id
+    pub fn valve_state(ctx: &Context, id: Id) -> Option<ValveState> {
+        ctx.data(|d| d.get_temp::<ValveState>(id.with("valve_state")))
+    }
+
+    fn update_valve_state(&self, ui: &Ui, id: Id) -> ValveState {
+        let state = self.compute_valve_state(ui, id);
+        ui.data_mut(|d| d.insert_temp(id.with("valve_state"), state));
+        state
+    }
+
+    fn compute_valve_state(&self, ui: &Ui, id: Id) -> ValveState {
+        let now = ui.input(|i| i.time);
+        let command_id = id.with("valve_command");
+        let command = match ui.data(|d| d.get_temp::<ValveCommand>(command_id)) {
+            Some(command) if command.on == *self.on => command,
+            // the first frame is not a transition
+            previous => ValveCommand {
+                on: *self.on,
+                since: if previous.is_some() {
+                    now
+                } else {
+                    f64::NEG_INFINITY
+                },
+            },
+        };
+        ui.data_mut(|d| d.insert_temp(command_id, command));
+
+        let remaining = self.travel_time.as_secs_f64() - (now - command.since);
+        let traveling = if command.on {
+            ValveState::Opening
+        } else {
+            ValveState::Closing
+        };
+        let reached = if command.on {
+            ValveState::Open
+        } else {
+            ValveState::Closed
+        };
+        if self.fault {
+            return ValveState::Fault;
+        }
+        let Some((open, closed)) = self.limit_switches else {
+            return if remaining > 0.0 { traveling } else { reached };
+        };
+        match (open, closed, command.on) {
+            (true, true, _) => ValveState::Fault,
+            (true, false, true) | (false, true, false) => reached,
+            _ if remaining > 0.0 => {
+                #[allow(clippy::cast_possible_truncation)]
+                ui.ctx().request_repaint_after_secs(remaining as f32);
+                traveling
+            }
+            _ => ValveState::Fault,
+        }
+    }
+
+    /// Label text for the state, the state text is added for the styles which do not display it
+    /// in the track
+    fn label_text(&self, on: bool) -> Option<String> {
+        let state = self
+            .state_text
+            .as_ref()
+            .filter(|_| !matches!(self.style, ToggleStyle::Button))
+            .map(|(on_text, off_text)| if on { on_text } else { off_text });
+        match (&self.label, state) {
+            (Some(label), Some(state)) => Some(format!("{} {}", label, state)),
+            (Some(label), None) => Some(label.clone()),
+            (None, Some(state)) => Some(state.clone()),
+            (None, None) => None,
+        }
+    }
 }
 
 impl egui::Widget for ToggleSwitch<'_> {
     #[allow(clippy::too_many_lines)]
     fn ui(self, ui: &mut Ui) -> egui::Response {
-        let has_user_defined_size = self.size.is_some();
-
         let default_toggle_size = match self.style {
             ToggleStyle::Button => vec2(2.0, 1.0),
-            ToggleStyle::Relay => vec2(3.0, 1.5),
+            ToggleStyle::Relay | ToggleStyle::RelayNc => vec2(3.0, 1.5),
             ToggleStyle::Valve => vec2(3.0, 3.0),
-        } * ui.spacing().interact_size.y;
-
-        let (label_size, label_spacing) = if let Some(ref label_text) = self.label {
-            let text_size = ui.fonts(|f| {
-                let galley = f.layout_no_wrap(
-                    label_text.to_string(),
-                    FontId::proportional(self.font_size),
-                    ui.visuals().text_color(),
-                );
-                galley.size()
-            });
-            (Some(text_size), ui.spacing().item_spacing.x)
+        } * ui.spacing().interact_size.y
+            * hit_scale(ui.ctx());
+
+        let font = FontId::proportional(self.font_size);
+        // measured for both states, so the layout does not change on toggle
+        let label_size = [true, false]
+            .into_iter()
+            .filter_map(|on| self.label_text(on))
+            .map(|text| {
+                ui.fonts(|f| {
+                    f.layout_no_wrap(text, font.clone(), ui.visuals().text_color())
+                        .size()
+                })
+            })
+            .reduce(Vec2::max);
+        let spacing = ui.spacing().item_spacing;
+        let position = self.label_position;
+
+        // the space taken by a label outside the switch
+        let label_space = match (label_size, position) {
+            (None, _) | (_, LabelPosition::Inside) => Vec2::ZERO,
+            (Some(size), LabelPosition::Left | LabelPosition::Right) => {
+                vec2(size.x + spacing.x, 0.0)
+            }
+            (Some(size), LabelPosition::Top | LabelPosition::Bottom) => {
+                vec2(0.0, size.y + spacing.y)
+            }
+        };
+        let text_size = label_size.unwrap_or_default();
+
+        let full_size = if let Some(size) = self.size {
+            size
         } else {
-            (None, 0.0)
+            match position {
+                LabelPosition::Left | LabelPosition::Right => vec2(
+                    default_toggle_size.x + label_space.x,
+                    default_toggle_size.y.max(text_size.y),
+                ),
+                LabelPosition::Top | LabelPosition::Bottom => vec2(
+                    default_toggle_size.x.max(text_size.x),
+                    default_toggle_size.y + label_space.y,
+                ),
+                LabelPosition::Inside => default_toggle_size,
+            }
         };
 
-        let full_size = if has_user_defined_size {
-            self.size.unwrap()
-        } else if let Some(text_size) = label_size {
+        let toggle_size = if self.size.is_some() {
+            let available = full_size - label_space;
             vec2(
-                default_toggle_size.x + label_spacing + text_size.x,
-                default_toggle_size.y.max(text_size.y),
+                if label_space.x > 0.0 {
+                    available.x.max(default_toggle_size.x * 0.6)
+                } else {
+                    full_size.x
+                },
+                if label_space.y > 0.0 {
+                    available.y.max(default_toggle_size.y * 0.6)
+                } else {
+                    full_size.y
+                },
             )
         } else {
             default_toggle_size
@@ -107,11 +317,14 @@ impl egui::Widget for ToggleSwitch<'_> {
 
         let (rect, mut response) = ui.allocate_exact_size(full_size, egui::Sense::click());
 
-        if response.clicked() {
+        if tapped(&response) {
             *self.on = !*self.on;
             response.mark_changed();
         }
 
+        let valve_state = matches!(self.style, ToggleStyle::Valve)
+            .then(|| self.update_valve_state(ui, response.id));
+
         response.widget_info(|| {
             egui::WidgetInfo::selected(
                 egui::WidgetType::Checkbox,
@@ -120,32 +333,46 @@ impl egui::Widget for ToggleSwitch<'_> {
                 self.label.as_ref().map_or("", |s| s.as_str()),
             )
         });
+        #[cfg(feature = "accesskit")]
+        if let Some(state) = valve_state {
+            ui.ctx().accesskit_node_builder(response.id, |node| {
+                node.set_state_description(state.description());
+            });
+        }
 
         if ui.is_rect_visible(rect) {
-            let stroke_color = if *self.on { SUCCESS } else { WARN };
+            let stroke_color = match valve_state {
+                Some(ValveState::Fault) => FAULT,
+                Some(ValveState::Open) => SUCCESS,
+                Some(ValveState::Closed) => WARN,
+                _ if *self.on => SUCCESS,
+                _ => WARN,
+            };
             let stroke = Stroke::new(1.0, stroke_color);
             let corner_radius = 4.0;
             let painter = ui.painter();
-            if matches!(self.style, ToggleStyle::Relay) {
+            if matches!(self.style, ToggleStyle::Relay | ToggleStyle::RelayNc) {
                 painter.rect_stroke(rect, corner_radius, stroke, StrokeKind::Inside);
             }
 
-            let toggle_size = if has_user_defined_size && label_size.is_some() {
-                let label_text_size = label_size.unwrap();
-                let available_width = full_size.x - label_spacing - label_text_size.x;
-                let toggle_width = available_width.max(default_toggle_size.x * 0.6);
-                vec2(toggle_width, full_size.y)
-            } else if has_user_defined_size {
-                full_size
-            } else {
-                default_toggle_size
+            let toggle_min = match position {
+                LabelPosition::Right => pos2(rect.left(), rect.center().y - toggle_size.y / 2.0),
+                LabelPosition::Left => pos2(
+                    rect.right() - toggle_size.x,
+                    rect.center().y - toggle_size.y / 2.0,
+                ),
+                LabelPosition::Top => pos2(
+                    rect.center().x - toggle_size.x / 2.0,
+                    rect.bottom() - toggle_size.y,
+                ),
+                LabelPosition::Bottom => pos2(rect.center().x - toggle_size.x / 2.0, rect.top()),
+                LabelPosition::Inside => rect.center() - toggle_size / 2.0,
             };
-
-            let rect_with_margin = egui::Rect::from_min_size(rect.min, toggle_size);
+            let rect_with_margin = egui::Rect::from_min_size(toggle_min, toggle_size);
             let inner_margin = if matches!(self.style, ToggleStyle::Button) {
                 0.0
             } else {
-                ui.spacing().item_spacing.x
+                spacing.x
             };
 
             if matches!(self.style, ToggleStyle::Valve) {
@@ -154,6 +381,33 @@ impl egui::Widget for ToggleSwitch<'_> {
 
             let toggle_rect = rect_with_margin.shrink(inner_margin);
 
+            // Space/Enter on the focused switch are handled by egui as clicks
+            if response.has_focus() {
+                let focus_stroke = ui.visuals().selection.stroke;
+                match self.style {
+                    ToggleStyle::Button => {
+                        let ring = toggle_rect.expand(2.0);
+                        painter.rect_stroke(
+                            ring,
+                            0.5 * ring.height(),
+                            focus_stroke,
+                            StrokeKind::Outside,
+                        );
+                    }
+                    ToggleStyle::Relay | ToggleStyle::RelayNc => {
+                        painter.rect_stroke(rect, corner_radius, focus_stroke, StrokeKind::Outside);
+                    }
+                    ToggleStyle::Valve => {
+                        painter.rect_stroke(
+                            rect_with_margin,
+                            corner_radius,
+                            focus_stroke,
+                            StrokeKind::Outside,
+                        );
+                    }
+                }
+            }
+
             let how_on = ui.ctx().animate_bool_responsive(response.id, *self.on);
 
             match self.style {
@@ -173,43 +427,18 @@ impl egui::Widget for ToggleSwitch<'_> {
                     let center = pos2(circle_x, toggle_rect.center().y);
                     painter.circle(center, 0.75 * radius, Color32::WHITE, Stroke::NONE);
                 }
-                ToggleStyle::Relay => {
-                    let center_y = toggle_rect.center().y;
-                    let circle_radius = toggle_rect.width() * 0.04;
-                    let node_left = toggle_rect.left() + toggle_rect.width() / 3.0;
-                    let node_right = toggle_rect.right() - toggle_rect.width() / 3.0;
-
-                    painter.line_segment(
-                        [
-                            pos2(toggle_rect.left(), center_y),
-                            pos2(node_left - circle_radius * 2.0, center_y),
-                        ],
+                ToggleStyle::Relay | ToggleStyle::RelayNc => {
+                    let normally_closed = matches!(self.style, ToggleStyle::RelayNc);
+                    let t = ui.ctx().animate_bool(response.id, *self.on);
+                    let closed = if normally_closed { 1.0 - t } else { t };
+                    paint_contact(
+                        painter,
+                        toggle_rect,
+                        closed,
+                        normally_closed,
                         stroke,
-                    );
-
-                    let circle_left = pos2(node_left - circle_radius, center_y);
-                    let circle_right = pos2(node_right + circle_radius, center_y);
-
-                    painter.circle_stroke(circle_left, circle_radius, stroke);
-                    painter.circle_stroke(circle_right, circle_radius, stroke);
-
-                    painter.line_segment(
-                        [
-                            pos2(toggle_rect.right(), center_y),
-                            pos2(node_right + circle_radius * 2.0, center_y),
-                        ],
                         stroke,
                     );
-
-                    let switch_left = pos2(node_left, center_y);
-                    let switch_right = pos2(node_right, center_y);
-                    let off_angle = 45.0_f32.to_radians();
-                    let t = ui.ctx().animate_bool(response.id, *self.on);
-                    let angle = egui::lerp(off_angle..=0.0, t);
-                    let length = (switch_right - switch_left).length();
-                    let dir = vec2(angle.cos(), angle.sin());
-                    let animated_left = switch_right - dir * length;
-                    painter.line_segment([animated_left, switch_right], stroke);
                 }
                 ToggleStyle::Valve => {
                     let center = toggle_rect.center();
@@ -242,21 +471,31 @@ impl egui::Widget for ToggleSwitch<'_> {
                         stroke,
                     ));
 
-                    let t = ui.ctx().animate_bool_with_time(response.id, *self.on, 3.0);
-
-                    if t > 0.0 && t < 1.0 {
-                        painter.circle(center, radius, ui.visuals().panel_fill, Stroke::NONE);
-                        let size = vec2(radius * 2.0, radius * 2.0);
-                        let spinner_rect = egui::Rect::from_center_size(center, size);
-                        let spinner = egui::Spinner::new()
-                            .size(radius * 2.0)
-                            .color(ui.visuals().panel_fill);
-                        spinner.paint_at(ui, spinner_rect);
-                    } else {
-                        painter.circle(center, radius, ui.visuals().panel_fill, stroke);
+                    match valve_state {
+                        Some(ValveState::Opening | ValveState::Closing) => {
+                            painter.circle(center, radius, ui.visuals().panel_fill, Stroke::NONE);
+                            let size = vec2(radius * 2.0, radius * 2.0);
+                            let spinner_rect = egui::Rect::from_center_size(center, size);
+                            let spinner =
+                                egui::Spinner::new().size(radius * 2.0).color(stroke_color);
+                            spinner.paint_at(ui, spinner_rect);
+                        }
+                        Some(ValveState::Fault) => {
+                            painter.circle(center, radius, ui.visuals().panel_fill, stroke);
+                            painter.text(
+                                center,
+                                Align2::CENTER_CENTER,
+                                "!",
+                                FontId::proportional(radius * 1.5),
+                                FAULT,
+                            );
+                        }
+                        _ => {
+                            painter.circle(center, radius, ui.visuals().panel_fill, stroke);
+                        }
                     }
 
-                    if !*self.on {
+                    if valve_state == Some(ValveState::Closed) {
                         painter.line_segment(
                             [
                                 pos2(center.x - valve_width / 2.0, center.y + valve_height / 4.0),
@@ -268,27 +507,69 @@ impl egui::Widget for ToggleSwitch<'_> {
                 }
             }
 
-            if let Some(label) = self.label {
-                let label_pos = if matches!(self.style, ToggleStyle::Valve) {
-                    pos2(rect.right(), rect.center().y)
-                } else {
-                    pos2(
-                        toggle_rect.right() + ui.spacing().item_spacing.x,
-                        toggle_rect.center().y,
-                    )
+            // text in the button track, on the side opposite to the knob
+            if matches!(self.style, ToggleStyle::Button) {
+                let state = self
+                    .state_text
+                    .as_ref()
+                    .map(|(on_text, off_text)| if *self.on { on_text } else { off_text });
+                let inside_label = self
+                    .label
+                    .as_ref()
+                    .filter(|_| position == LabelPosition::Inside);
+                let track_text = match (inside_label, state) {
+                    (Some(label), Some(state)) => Some(format!("{} {}", label, state)),
+                    (Some(text), None) | (None, Some(text)) => Some(text.clone()),
+                    (None, None) => None,
                 };
-                let anchor = if matches!(self.style, ToggleStyle::Valve) {
-                    Align2::RIGHT_CENTER
-                } else {
-                    Align2::LEFT_CENTER
+                if let Some(text) = track_text {
+                    let knob = toggle_rect.height();
+                    let free_center = |knob_left: bool| {
+                        if knob_left {
+                            (toggle_rect.left() + knob + toggle_rect.right()) / 2.0
+                        } else {
+                            (toggle_rect.left() + toggle_rect.right() - knob) / 2.0
+                        }
+                    };
+                    let x = egui::lerp(free_center(true)..=free_center(false), how_on);
+                    painter.text(
+                        pos2(x, toggle_rect.center().y),
+                        Align2::CENTER_CENTER,
+                        text,
+                        FontId::proportional(toggle_rect.height() * 0.5),
+                        GRAY_DARK,
+                    );
+                }
+            }
+
+            if let Some(label) = self.label_text(*self.on) {
+                let placement = match position {
+                    LabelPosition::Right => Some((
+                        pos2(rect_with_margin.right() + spacing.x, rect.center().y),
+                        Align2::LEFT_CENTER,
+                    )),
+                    LabelPosition::Left => Some((
+                        pos2(rect_with_margin.left() - spacing.x, rect.center().y),
+                        Align2::RIGHT_CENTER,
+                    )),
+                    LabelPosition::Top => Some((
+                        pos2(rect.center().x, rect_with_margin.top() - spacing.y),
+                        Align2::CENTER_BOTTOM,
+                    )),
+                    LabelPosition::Bottom => Some((
+                        pos2(rect.center().x, rect_with_margin.bottom() + spacing.y),
+                        Align2::CENTER_TOP,
+                    )),
+                    // the button displays the inside label in the track
+                    LabelPosition::Inside if !matches!(self.style, ToggleStyle::Button) => Some((
+                        pos2(rect_with_margin.center().x, rect_with_margin.bottom() - 2.0),
+                        Align2::CENTER_BOTTOM,
+                    )),
+                    LabelPosition::Inside => None,
                 };
-                painter.text(
-                    label_pos,
-                    anchor,
-                    label,
-                    FontId::proportional(self.font_size),
-                    get_text_color(ui),
-                );
+                if let Some((label_pos, anchor)) = placement {
+                    painter.text(label_pos, anchor, label, font, get_text_color(ui));
+                }
             }
         }
 
@@ -0,0 +1,301 @@
+use core::fmt;
+
+/// Formats numeric values for labels, ticks and readouts
+pub trait ValueFormatter {
+    /// Format the value
+    fn format(&self, value: f64) -> String;
+}
+
+impl<F> ValueFormatter for F
+where
+    F: Fn(f64) -> String,
+{
+    fn format(&self, value: f64) -> String {
+        self(value)
+    }
+}
+
+const AUTO_SIGNIFICANT_DIGITS: i32 = 6;
+const AUTO_MAX_PRECISION: i32 = 9;
+
+const SI_PREFIXES: [(i32, &str); 9] = [
+    (-12, "p"),
+    (-9, "n"),
+    (-6, "µ"),
+    (-3, "m"),
+    (0, ""),
+    (3, "k"),
+    (6, "M"),
+    (9, "G"),
+    (12, "T"),
+];
+
+/// Configurable number formatter
+///
+/// By default, values are formatted with up to 6 significant digits and trailing zeros removed,
+/// so ranges like `0.0..=1.0` with 0.1 steps are displayed as `0.1`, `0.2` etc.
+#[derive(Clone, Debug)]
+pub struct NumberFormat {
+    precision: Option<usize>,
+    suffix: Option<String>,
+    si_prefix: bool,
+    thousands_separator: Option<char>,
+    decimal_separator: char,
+    hex: Option<usize>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NumberFormat {
+    /// Create a new number formatter with automatic precision
+    pub fn new() -> Self {
+        Self {
+            precision: None,
+            suffix: None,
+            si_prefix: false,
+            thousands_separator: None,
+            decimal_separator: '.',
+            hex: None,
+        }
+    }
+
+    /// Set the fixed number of digits after the decimal separator
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Set the engineering units suffix (e.g. `°C`, `bar`), separated from the number with a space
+    pub fn suffix(mut self, suffix: impl fmt::Display) -> Self {
+        self.suffix = Some(suffix.to_string());
+        self
+    }
+
+    /// Scale the value with SI prefixes (k, M, m, µ etc.), the prefix is put before the suffix
+    pub fn si_prefix(mut self, si_prefix: bool) -> Self {
+        self.si_prefix = si_prefix;
+        self
+    }
+
+    /// Set the thousands separator (e.g. `,` or a space)
+    pub fn thousands_separator(mut self, separator: char) -> Self {
+        self.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Set the decimal separator (`.` by default)
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Use the decimal comma, as in most European locales
+    pub fn decimal_comma(self) -> Self {
+        self.decimal_separator(',')
+    }
+
+    /// Format values as hexadecimal integers (e.g. register values), zero-padded to the given
+    /// number of digits. If the thousands separator is set, digits are grouped by 4
+    pub fn hex(mut self, width: usize) -> Self {
+        self.hex = Some(width);
+        self
+    }
+
+    fn format_hex(&self, value: f64, width: usize) -> String {
+        #[allow(clippy::cast_possible_truncation)]
+        let v = value.round() as i64;
+        let sign = if v < 0 { "-" } else { "" };
+        let digits = format!("{:0width$X}", v.unsigned_abs(), width = width);
+        let s = format!("{}0x{}", sign, self.group_digits(&digits, 4));
+        self.with_suffix(s, "")
+    }
+
+    fn group_digits(&self, digits: &str, group: usize) -> String {
+        let Some(separator) = self.thousands_separator else {
+            return digits.to_owned();
+        };
+        // the digits are always ASCII
+        let groups: Vec<&str> = digits
+            .as_bytes()
+            .rchunks(group)
+            .rev()
+            .map(|chunk| core::str::from_utf8(chunk).unwrap_or_default())
+            .collect();
+        groups.join(&separator.to_string())
+    }
+
+    fn format_number(&self, value: f64) -> String {
+        let mut s = if let Some(precision) = self.precision {
+            format!("{:.*}", precision, value)
+        } else {
+            let mut s = format!("{:.*}", auto_precision(value), value);
+            if s.contains('.') {
+                s.truncate(s.trim_end_matches('0').trim_end_matches('.').len());
+            }
+            s
+        };
+        // negative values rounded to zero (e.g. -0.01 -> -0.0)
+        if s.strip_prefix('-')
+            .is_some_and(|v| v.chars().all(|c| c == '0' || c == '.'))
+        {
+            s.remove(0);
+        }
+        s
+    }
+
+    fn with_suffix(&self, mut s: String, prefix: &str) -> String {
+        let suffix = self.suffix.as_deref().unwrap_or_default();
+        if !prefix.is_empty() || !suffix.is_empty() {
+            s.push(' ');
+            s.push_str(prefix);
+            s.push_str(suffix);
+        }
+        s
+    }
+}
+
+impl ValueFormatter for NumberFormat {
+    fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return self.with_suffix(value.to_string(), "");
+        }
+        if let Some(width) = self.hex {
+            return self.format_hex(value, width);
+        }
+        let (formatted, prefix) = if self.si_prefix {
+            let mut index = si_index(value);
+            let mut formatted = self.format_number(value / 10f64.powi(SI_PREFIXES[index].0));
+            // rounding may carry the mantissa to 1000 (e.g. 999999.7 -> 1000 k)
+            if index + 1 < SI_PREFIXES.len()
+                && formatted.parse::<f64>().is_ok_and(|v| v.abs() >= 1000.0)
+            {
+                index += 1;
+                formatted = self.format_number(value / 10f64.powi(SI_PREFIXES[index].0));
+            }
+            (formatted, SI_PREFIXES[index].1)
+        } else {
+            (self.format_number(value), "")
+        };
+        let (sign, unsigned) = formatted
+            .strip_prefix('-')
+            .map_or(("", formatted.as_str()), |s| ("-", s));
+        let (int_part, frac_part) = unsigned
+            .split_once('.')
+            .map_or((unsigned, None), |(i, f)| (i, Some(f)));
+        let mut s = format!("{}{}", sign, self.group_digits(int_part, 3));
+        if let Some(frac) = frac_part {
+            s.push(self.decimal_separator);
+            s.push_str(frac);
+        }
+        self.with_suffix(s, prefix)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn auto_precision(value: f64) -> usize {
+    if value == 0.0 {
+        return 0;
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    (AUTO_SIGNIFICANT_DIGITS - 1 - magnitude).clamp(0, AUTO_MAX_PRECISION) as usize
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn si_index(value: f64) -> usize {
+    if value == 0.0 {
+        return SI_PREFIXES.len() / 2;
+    }
+    let exponent = (value.abs().log10().floor() as i32).div_euclid(3) * 3;
+    SI_PREFIXES
+        .iter()
+        .rposition(|(exp, _)| *exp <= exponent)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NumberFormat, ValueFormatter};
+
+    #[test]
+    fn test_auto_precision() {
+        let f = NumberFormat::new();
+        assert_eq!(f.format(0.1 + 0.2), "0.3");
+        assert_eq!(f.format(12.5), "12.5");
+        assert_eq!(f.format(1_234_567.0), "1234567");
+        assert_eq!(f.format(0.0), "0");
+        assert_eq!(f.format(-0.000_000_000_1), "0");
+    }
+
+    #[test]
+    fn test_fixed_precision() {
+        let f = NumberFormat::new().precision(2);
+        assert_eq!(f.format(1.005_1), "1.01");
+        assert_eq!(f.format(2.0), "2.00");
+        assert_eq!(f.format(-1.5), "-1.50");
+    }
+
+    #[test]
+    fn test_negative_zero() {
+        assert_eq!(NumberFormat::new().precision(1).format(-0.01), "0.0");
+        assert_eq!(NumberFormat::new().precision(0).format(-0.4), "0");
+        assert_eq!(NumberFormat::new().precision(1).format(-0.06), "-0.1");
+    }
+
+    #[test]
+    fn test_separators() {
+        let f = NumberFormat::new()
+            .precision(2)
+            .thousands_separator(' ')
+            .decimal_comma();
+        assert_eq!(f.format(-1_234_567.891), "-1 234 567,89");
+        assert_eq!(f.format(999.0), "999,00");
+    }
+
+    #[test]
+    fn test_si_prefix() {
+        let f = NumberFormat::new().si_prefix(true).suffix("Pa");
+        assert_eq!(f.format(0.0), "0 Pa");
+        assert_eq!(f.format(1500.0), "1.5 kPa");
+        assert_eq!(f.format(-2_500_000.0), "-2.5 MPa");
+        assert_eq!(f.format(0.000_25), "250 µPa");
+        assert_eq!(f.format(1e18), "1000000 TPa");
+    }
+
+    #[test]
+    fn test_si_prefix_carry() {
+        let f = NumberFormat::new().si_prefix(true).suffix("Pa");
+        assert_eq!(f.format(999_999.7), "1 MPa");
+        assert_eq!(f.format(999.999_999_9), "1 kPa");
+        assert_eq!(f.format(-999.999_999_9), "-1 kPa");
+        let f = NumberFormat::new().si_prefix(true).precision(1);
+        assert_eq!(f.format(999.96), "1.0 k");
+        assert_eq!(f.format(999.94), "999.9");
+    }
+
+    #[test]
+    fn test_hex() {
+        let f = NumberFormat::new().hex(4);
+        assert_eq!(f.format(255.0), "0x00FF");
+        assert_eq!(f.format(-1.0), "-0x0001");
+        assert_eq!(f.format(1_193_046.0), "0x123456");
+        assert_eq!(
+            NumberFormat::new()
+                .hex(8)
+                .thousands_separator('_')
+                .format(65535.0),
+            "0x0000_FFFF"
+        );
+    }
+
+    #[test]
+    fn test_hex_clamping() {
+        let f = NumberFormat::new().hex(0);
+        assert_eq!(f.format(1e30), "0x7FFFFFFFFFFFFFFF");
+        assert_eq!(f.format(-1e30), "-0x8000000000000000");
+        assert_eq!(f.format(f64::NAN), "NaN");
+    }
+}
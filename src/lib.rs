@@ -1,10 +1,47 @@
 #![deny(missing_docs)]
 #![ doc = include_str!( concat!( env!( "CARGO_MANIFEST_DIR" ), "/", "README.md" ) ) ]
 
+mod accessibility;
+mod animation;
 mod colors;
 mod components;
+mod format;
+mod numeric;
+mod peak;
+mod scale;
+mod ticks;
+mod touch;
+mod units;
 
+pub use accessibility::{AlarmState, Quality};
+pub use animation::Damping;
+pub use components::ArcCap;
 pub use components::Bar;
+pub use components::CoilKind;
+pub use components::Compass;
+pub use components::CompassMode;
+pub use components::ControlValve;
+pub use components::FailAction;
 pub use components::Gauge;
+pub use components::GaugeStyle;
+pub use components::GaugeText;
+pub use components::KeyboardLayout;
+pub use components::Knob;
+pub use components::KnobDrag;
+pub use components::LabelPosition;
+pub use components::LadderRung;
+pub use components::NeedleShape;
+pub use components::OnScreenKeyboard;
+pub use components::SetpointEntry;
+pub use components::TickLabelOrientation;
+pub use components::TimerKind;
 pub use components::ToggleStyle;
 pub use components::ToggleSwitch;
+pub use components::ValveMode;
+pub use components::ValveState;
+pub use format::{NumberFormat, ValueFormatter};
+pub use numeric::Numeric;
+pub use peak::{reset_peak_hold, PeakHold};
+pub use scale::Scale;
+pub use touch::{long_pressed, set_touch_profile, TouchProfile};
+pub use units::{Quantity, Unit, UnitSystem};
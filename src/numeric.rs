@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+/// Numeric value, accepted by the value components
+///
+/// Implemented for all primitive numbers and [`Duration`] (displayed in seconds). Integers are
+/// converted to `f64` which is exact up to 2^53.
+pub trait Numeric: Copy {
+    /// `true` for integer types, the default labels of such values are formatted without decimals
+    const INTEGRAL: bool;
+
+    /// Convert the value to `f64`
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($integral: expr, $($t: ty),*) => {
+        $(
+            impl Numeric for $t {
+                const INTEGRAL: bool = $integral;
+
+                #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(true, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_numeric!(false, f32, f64);
+
+impl Numeric for Duration {
+    const INTEGRAL: bool = false;
+
+    fn to_f64(self) -> f64 {
+        self.as_secs_f64()
+    }
+}
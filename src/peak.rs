@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use egui::{Context, Id, Response, Ui};
+
+use crate::touch::double_tapped;
+
+/// Peak-hold (min/max marker) mode
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PeakHold {
+    /// The markers are held until reset
+    Hold,
+    /// The markers are held for the given time, then decay towards the current value
+    Decay {
+        /// Hold time
+        hold: Duration,
+        /// Decay rate, fraction of the range per second
+        rate: f64,
+    },
+}
+
+/// Resets peak-hold markers of the component with the given id (see `id` methods of the
+/// components). The markers can also be reset by double-clicking the component
+pub fn reset_peak_hold(ctx: &Context, id: Id) {
+    ctx.data_mut(|d| d.remove::<PeakState>(id));
+}
+
+/// Peak-hold configuration of a component
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PeakHoldConfig {
+    pub(crate) mode: PeakHold,
+    pub(crate) min: bool,
+}
+
+/// Remembered min/max values, stored in egui memory
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PeakState {
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+    min_time: f64,
+    max_time: f64,
+    updated: f64,
+}
+
+impl PeakState {
+    /// Updates the state of the component and returns the markers. `span` is the value range
+    /// size, used for decay
+    pub(crate) fn update(
+        ui: &Ui,
+        response: &Response,
+        id: Id,
+        config: PeakHoldConfig,
+        value: f64,
+        span: f64,
+    ) -> Self {
+        if double_tapped(response) {
+            reset_peak_hold(ui.ctx(), id);
+        }
+        let now = ui.input(|i| i.time);
+        let mut state = ui
+            .data(|d| d.get_temp::<PeakState>(id))
+            .unwrap_or(PeakState {
+                min: value,
+                max: value,
+                min_time: now,
+                max_time: now,
+                updated: now,
+            });
+        if value >= state.max {
+            state.max = value;
+            state.max_time = now;
+        }
+        if value <= state.min {
+            state.min = value;
+            state.min_time = now;
+        }
+        if let PeakHold::Decay { hold, rate } = config.mode {
+            let decay = span.abs() * rate * (now - state.updated);
+            let hold = hold.as_secs_f64();
+            if now - state.max_time > hold {
+                state.max = (state.max - decay).max(value);
+            }
+            if now - state.min_time > hold {
+                state.min = (state.min + decay).min(value);
+            }
+            if state.max > value || state.min < value {
+                ui.ctx().request_repaint();
+            }
+        }
+        state.updated = now;
+        ui.data_mut(|d| d.insert_temp(id, state));
+        state
+    }
+}
@@ -0,0 +1,139 @@
+/// Value scale of the components
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Scale {
+    /// Linear scale
+    #[default]
+    Linear,
+    /// Logarithmic (base 10) scale, the range must be positive
+    Log10,
+    /// Square-root scale (e.g. flow measured by differential pressure)
+    Sqrt,
+    /// Piecewise linear scale, defined by a table of `(value, position)` points, where position
+    /// is in `0.0..=1.0`. The table values are used as the major ticks
+    Piecewise(Vec<(f64, f64)>),
+}
+
+impl Scale {
+    /// Normalized position (`0.0..=1.0`) of the value in the range
+    pub fn normalize(&self, value: f64, min: f64, max: f64) -> f64 {
+        let position = match self {
+            Scale::Linear => (value - min) / (max - min),
+            Scale::Log10 => {
+                let log = |v: f64| v.max(f64::MIN_POSITIVE).log10();
+                (log(value) - log(min)) / (log(max) - log(min))
+            }
+            Scale::Sqrt => ((value - min) / (max - min)).max(0.0).sqrt(),
+            Scale::Piecewise(table) => interpolate(table.iter().copied(), value),
+        };
+        if position.is_nan() {
+            0.0
+        } else {
+            position.clamp(0.0, 1.0)
+        }
+    }
+
+    /// The value at the normalized position (`0.0..=1.0`) in the range
+    pub fn denormalize(&self, position: f64, min: f64, max: f64) -> f64 {
+        let position = position.clamp(0.0, 1.0);
+        match self {
+            Scale::Linear => min + (max - min) * position,
+            Scale::Log10 => {
+                let log = |v: f64| v.max(f64::MIN_POSITIVE).log10();
+                10f64.powf(log(min) + (log(max) - log(min)) * position)
+            }
+            Scale::Sqrt => min + (max - min) * position * position,
+            Scale::Piecewise(table) => interpolate(table.iter().map(|(v, p)| (*p, *v)), position),
+        }
+    }
+
+    /// Applies a (monotonic) value conversion to the scale table
+    pub(crate) fn convert_values(&mut self, f: impl Fn(f64) -> f64) {
+        if let Scale::Piecewise(table) = self {
+            for (value, _) in table {
+                *value = f(*value);
+            }
+        }
+    }
+}
+
+/// Linear interpolation in the table of (x, y) points, clamped at the table ends
+fn interpolate(points: impl Iterator<Item = (f64, f64)>, x: f64) -> f64 {
+    let mut points: Vec<(f64, f64)> = points.collect();
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return 0.0;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+    for w in points.windows(2) {
+        let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+        if x <= x1 {
+            if x1 - x0 <= 0.0 {
+                return y1;
+            }
+            return y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        }
+    }
+    last.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scale;
+
+    fn piecewise() -> Scale {
+        Scale::Piecewise(vec![(0.0, 0.0), (10.0, 0.5), (100.0, 1.0)])
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert!((Scale::Linear.normalize(25.0, 0.0, 100.0) - 0.25).abs() < 1e-12);
+        assert!((Scale::Log10.normalize(10.0, 1.0, 1000.0) - 1.0 / 3.0).abs() < 1e-12);
+        assert!((Scale::Sqrt.normalize(25.0, 0.0, 100.0) - 0.5).abs() < 1e-12);
+        assert!((piecewise().normalize(55.0, 0.0, 100.0) - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normalize_clamped() {
+        for scale in [Scale::Linear, Scale::Log10, Scale::Sqrt, piecewise()] {
+            assert!(scale.normalize(-50.0, 1.0, 100.0).abs() < f64::EPSILON);
+            assert!((scale.normalize(500.0, 1.0, 100.0) - 1.0).abs() < f64::EPSILON);
+        }
+        assert!(Scale::Linear.normalize(5.0, 5.0, 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for scale in [Scale::Linear, Scale::Log10, Scale::Sqrt, piecewise()] {
+            for value in [1.0, 2.5, 10.0, 42.0, 99.9] {
+                let position = scale.normalize(value, 1.0, 100.0);
+                let restored = scale.denormalize(position, 1.0, 100.0);
+                assert!(
+                    (restored - value).abs() < 1e-9,
+                    "{scale:?}: {value} -> {position} -> {restored}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_reversed_range_round_trip() {
+        let position = Scale::Linear.normalize(30.0, 100.0, 0.0);
+        assert!((position - 0.7).abs() < 1e-12);
+        assert!((Scale::Linear.denormalize(position, 100.0, 0.0) - 30.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_convert_values() {
+        let mut scale = piecewise();
+        scale.convert_values(|v| v * 2.0);
+        assert_eq!(
+            scale,
+            Scale::Piecewise(vec![(0.0, 0.0), (20.0, 0.5), (200.0, 1.0)])
+        );
+    }
+}
@@ -0,0 +1,250 @@
+use crate::scale::Scale;
+
+/// Major and minor tick values of a scale
+#[derive(Default, Debug, Clone)]
+pub(crate) struct Ticks {
+    pub(crate) major: Vec<f64>,
+    pub(crate) minor: Vec<f64>,
+}
+
+impl Ticks {
+    /// Generates ticks for the scale
+    pub(crate) fn new(
+        scale: &Scale,
+        min: f64,
+        max: f64,
+        max_major: usize,
+        minor_divisions: usize,
+        integral: bool,
+    ) -> Self {
+        match scale {
+            Scale::Linear | Scale::Sqrt => {
+                Self::linear(min, max, max_major, minor_divisions, integral)
+            }
+            Scale::Log10 => Self::log10(min, max, max_major, minor_divisions, integral),
+            Scale::Piecewise(table) => {
+                if max_major < 2 {
+                    return Self::default();
+                }
+                let (min, max) = if min <= max { (min, max) } else { (max, min) };
+                let mut major: Vec<f64> = table
+                    .iter()
+                    .map(|(v, _)| *v)
+                    .filter(|v| (min..=max).contains(v))
+                    .collect();
+                major.sort_by(f64::total_cmp);
+                major.dedup();
+                Self {
+                    major,
+                    minor: Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// Generates ticks at the decades, minor ticks are placed at 2..9 multiples of the decades if
+    /// enabled. Falls back to linear ticks if the range is less than a decade
+    #[allow(clippy::cast_possible_truncation)]
+    fn log10(min: f64, max: f64, max_major: usize, minor_divisions: usize, integral: bool) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        if max_major < 2 || min <= 0.0 {
+            return Self::default();
+        }
+        let first = min.log10().ceil() as i32;
+        let last = max.log10().floor() as i32;
+        if last <= first {
+            return Self::linear(min, max, max_major, minor_divisions, integral);
+        }
+        let decades = usize::try_from(last - first).unwrap_or_default() + 1;
+        let every = i32::try_from(decades.div_ceil(max_major)).unwrap_or(1);
+        let major: Vec<f64> = (first..=last)
+            .filter(|e| (e - first) % every == 0)
+            .map(|e| 10f64.powi(e))
+            .collect();
+        let minor = if minor_divisions > 1 {
+            (first - 1..=last)
+                .flat_map(|e| (1..10).map(move |m| f64::from(m) * 10f64.powi(e)))
+                .filter(|v| (min..=max).contains(v))
+                .filter(|v| !major.iter().any(|m| (m - v).abs() < m * 1e-9))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self { major, minor }
+    }
+
+    /// Generates ticks with "nice" (1-2-5) major steps, at most `max_major` major ticks and
+    /// `minor_divisions` minor intervals per major step. Steps of integral values are never
+    /// less than 1
+    fn linear(
+        min: f64,
+        max: f64,
+        max_major: usize,
+        minor_divisions: usize,
+        integral: bool,
+    ) -> Self {
+        let (min, max) = if min <= max { (min, max) } else { (max, min) };
+        if max_major < 2 || !(max - min).is_normal() {
+            return Self::default();
+        }
+        let step = nice_step(max - min, max_major);
+        let step = if integral { step.max(1.0) } else { step };
+        let major = steps_within(min, max, step);
+        let minor = if minor_divisions > 1 {
+            #[allow(clippy::cast_precision_loss)]
+            let minor_step = step / minor_divisions as f64;
+            let minor_step = if integral {
+                integral_minor_step(step, minor_step)
+            } else {
+                minor_step
+            };
+            steps_within(min, max, minor_step)
+                .into_iter()
+                .filter(|v| !major.iter().any(|m| (m - v).abs() < minor_step / 2.0))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Self { major, minor }
+    }
+}
+
+/// Finds the 1-2-5 step, producing at most `max_ticks` ticks in the range
+pub(crate) fn nice_step(range: f64, max_ticks: usize) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let raw = range / (max_ticks.max(2) - 1) as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let residual = raw / magnitude;
+    let nice = if residual <= 1.0 {
+        1.0
+    } else if residual <= 2.0 {
+        2.0
+    } else if residual <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+/// Minor step of integral values, so minor ticks stay integral and aligned with the major ones:
+/// the smallest whole 1-2-5 fraction of the major step (1/10, 1/5, 1/4 or 1/2), not below the
+/// preferred step, or the largest one below it. The major step (no minor ticks) if none is whole
+fn integral_minor_step(step: f64, preferred: f64) -> f64 {
+    let whole: Vec<f64> = [10.0, 5.0, 4.0, 2.0]
+        .into_iter()
+        .map(|divisor| step / divisor)
+        .filter(|minor| *minor >= 1.0 && minor.fract() == 0.0)
+        .collect();
+    whole
+        .iter()
+        .find(|minor| **minor >= preferred)
+        .or(whole.last())
+        .copied()
+        .unwrap_or(step)
+}
+
+/// All multiples of the step within the range
+#[allow(clippy::cast_possible_truncation)]
+fn steps_within(min: f64, max: f64, step: f64) -> Vec<f64> {
+    let eps = step * 1e-9;
+    let first = ((min - eps) / step).ceil() as i64;
+    let last = ((max + eps) / step).floor() as i64;
+    #[allow(clippy::cast_precision_loss)]
+    (first..=last)
+        .map(|i| {
+            let v = i as f64 * step;
+            // suppress negative zero and float noise around zero
+            if v.abs() < eps {
+                0.0
+            } else {
+                v
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{nice_step, Ticks};
+    use crate::scale::Scale;
+
+    #[test]
+    fn test_nice_step() {
+        assert!((nice_step(100.0, 11) - 10.0).abs() < 1e-12);
+        assert!((nice_step(100.0, 6) - 20.0).abs() < 1e-12);
+        assert!((nice_step(1.0, 6) - 0.2).abs() < 1e-12);
+        assert!((nice_step(3.0, 7) - 0.5).abs() < 1e-12);
+        assert!((nice_step(70.0, 5) - 20.0).abs() < 1e-12);
+        assert!((nice_step(0.003, 4) - 0.001).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_linear() {
+        let ticks = Ticks::new(&Scale::Linear, 0.0, 1.0, 6, 0, false);
+        let expected = [0.0, 0.2, 0.4, 0.6, 0.8, 1.0];
+        assert_eq!(ticks.major.len(), expected.len());
+        assert!(ticks
+            .major
+            .iter()
+            .zip(expected)
+            .all(|(v, e)| (v - e).abs() < 1e-12));
+        assert!(ticks.minor.is_empty());
+        let ticks = Ticks::new(&Scale::Linear, 100.0, -100.0, 5, 2, false);
+        assert_eq!(ticks.major, [-100.0, -50.0, 0.0, 50.0, 100.0]);
+        assert_eq!(ticks.minor, [-75.0, -25.0, 25.0, 75.0]);
+    }
+
+    #[test]
+    fn test_linear_no_negative_zero() {
+        let ticks = Ticks::new(&Scale::Linear, -0.3, 0.3, 7, 0, false);
+        assert!(ticks
+            .major
+            .iter()
+            .all(|v| !(*v == 0.0 && v.is_sign_negative())));
+        assert!(ticks.major.contains(&0.0));
+    }
+
+    #[test]
+    fn test_linear_integral() {
+        let ticks = Ticks::new(&Scale::Linear, 0.0, 3.0, 7, 4, true);
+        assert_eq!(ticks.major, [0.0, 1.0, 2.0, 3.0]);
+        assert!(ticks.minor.is_empty());
+        let ticks = Ticks::new(&Scale::Linear, 0.0, 100.0, 6, 3, true);
+        assert_eq!(ticks.minor, [10.0, 30.0, 50.0, 70.0, 90.0]);
+        let ticks = Ticks::new(&Scale::Linear, 0.0, 20.0, 6, 4, true);
+        assert_eq!(ticks.major, [0.0, 5.0, 10.0, 15.0, 20.0]);
+        assert_eq!(
+            ticks.minor,
+            [
+                1.0, 2.0, 3.0, 4.0, 6.0, 7.0, 8.0, 9.0, 11.0, 12.0, 13.0, 14.0, 16.0, 17.0, 18.0,
+                19.0
+            ]
+        );
+    }
+
+    #[test]
+    fn test_linear_integral_large_range() {
+        for max in [f64::from(u32::MAX), 1e12, 9.007_199_254_740_991e15] {
+            let ticks = Ticks::new(&Scale::Linear, 0.0, max, 5, 3, true);
+            assert!(!ticks.major.is_empty());
+            assert!(!ticks.minor.is_empty());
+            assert!(ticks.minor.len() <= ticks.major.len() * 3);
+            assert!(ticks
+                .major
+                .iter()
+                .chain(&ticks.minor)
+                .all(|v| v.fract() == 0.0));
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert!(Ticks::new(&Scale::Linear, 5.0, 5.0, 10, 2, false)
+            .major
+            .is_empty());
+        assert!(Ticks::new(&Scale::Linear, 0.0, 10.0, 1, 2, false)
+            .major
+            .is_empty());
+    }
+}
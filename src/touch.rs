@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use egui::{Context, Id, PointerButton, Response};
+
+/// Touch profile of the ehmi widgets, set per egui context with [`set_touch_profile`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchProfile {
+    hit_scale: f32,
+    long_press: Duration,
+    tap_slop: f32,
+    scroll_guard: Duration,
+}
+
+impl Default for TouchProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TouchProfile {
+    /// Create a new profile with the desktop (mouse) defaults
+    pub fn new() -> Self {
+        Self {
+            hit_scale: 1.0,
+            long_press: Duration::from_millis(800),
+            tap_slop: 6.0,
+            scroll_guard: Duration::ZERO,
+        }
+    }
+
+    /// Profile for touch panels: larger widgets and keys, shorter long-press, taps shortly after
+    /// scrolling are ignored
+    pub fn touch() -> Self {
+        Self {
+            hit_scale: 1.75,
+            long_press: Duration::from_millis(600),
+            tap_slop: 10.0,
+            scroll_guard: Duration::from_millis(300),
+        }
+    }
+
+    /// Set the scale of the default sizes of the interactive widgets (switches, knobs, entries,
+    /// keypads). Explicitly sized widgets keep their size
+    pub fn hit_scale(mut self, scale: f32) -> Self {
+        self.hit_scale = scale.max(0.1);
+        self
+    }
+
+    /// Set the long-press duration. A press, held longer, is not a click
+    pub fn long_press(mut self, duration: Duration) -> Self {
+        self.long_press = duration;
+        self
+    }
+
+    /// Set the max pointer travel of a tap, in points. A pointer moving further (e.g. dragging a
+    /// scroll area) does not click
+    pub fn tap_slop(mut self, distance: f32) -> Self {
+        self.tap_slop = distance;
+        self
+    }
+
+    /// Set the time after scrolling, while the taps are ignored
+    pub fn scroll_guard(mut self, duration: Duration) -> Self {
+        self.scroll_guard = duration;
+        self
+    }
+}
+
+fn profile_id() -> Id {
+    Id::new("ehmi_touch_profile")
+}
+
+/// Sets the touch profile of the context. Also updates the egui click distance and duration, so
+/// the profile applies to the egui own widgets as well
+pub fn set_touch_profile(ctx: &Context, profile: TouchProfile) {
+    ctx.data_mut(|d| d.insert_temp(profile_id(), profile));
+    ctx.options_mut(|o| {
+        o.input_options.max_click_dist = profile.tap_slop;
+        o.input_options.max_click_duration = profile.long_press.as_secs_f64();
+    });
+}
+
+/// Current touch profile of the context
+pub(crate) fn touch_profile(ctx: &Context) -> TouchProfile {
+    ctx.data(|d| d.get_temp::<TouchProfile>(profile_id()))
+        .unwrap_or_default()
+}
+
+/// Scale of the default sizes of the interactive widgets
+pub(crate) fn hit_scale(ctx: &Context) -> f32 {
+    touch_profile(ctx).hit_scale
+}
+
+fn scroll_guard_passed(response: &Response) -> bool {
+    response.ctx.input(egui::InputState::time_since_last_scroll)
+        >= touch_profile(&response.ctx).scroll_guard.as_secs_f32()
+}
+
+/// The widget has been clicked (tapped), unless the tap came right after scrolling. Keyboard
+/// (Space/Enter) and accessibility clicks are not guarded
+pub(crate) fn tapped(response: &Response) -> bool {
+    if response.clicked_by(PointerButton::Primary) {
+        scroll_guard_passed(response)
+    } else {
+        response.clicked()
+    }
+}
+
+/// The widget has been double-clicked (double-tapped), unless the taps came right after
+/// scrolling. Presses held for the long-press duration are not taps, as for [`tapped`]
+pub(crate) fn double_tapped(response: &Response) -> bool {
+    response.double_clicked() && scroll_guard_passed(response)
+}
+
+/// Returns `true` (once per press) if the widget has been pressed and held without moving for
+/// the long-press duration of the touch profile. Works with both touch and mouse input, use it
+/// for secondary actions, e.g. context menus or faceplates
+pub fn long_pressed(response: &Response) -> bool {
+    let ctx = &response.ctx;
+    if response.long_touched() {
+        return true;
+    }
+    if !response.is_pointer_button_down_on() {
+        return false;
+    }
+    let profile = touch_profile(ctx);
+    let (now, press) = ctx.input(|i| {
+        let moved = i
+            .pointer
+            .press_origin()
+            .zip(i.pointer.latest_pos())
+            .is_some_and(|(origin, pos)| origin.distance(pos) > profile.tap_slop);
+        (i.time, i.pointer.press_start_time().filter(|_| !moved))
+    });
+    let Some(press_start) = press else {
+        return false;
+    };
+    let fired_id = response.id.with("long_press");
+    if ctx
+        .data(|d| d.get_temp::<f64>(fired_id))
+        .is_some_and(|fired| fired >= press_start)
+    {
+        return false;
+    }
+    let remaining = profile.long_press.as_secs_f64() - (now - press_start);
+    if remaining > 0.0 {
+        #[allow(clippy::cast_possible_truncation)]
+        ctx.request_repaint_after_secs(remaining as f32);
+        return false;
+    }
+    ctx.data_mut(|d| d.insert_temp(fired_id, press_start));
+    true
+}
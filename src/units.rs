@@ -0,0 +1,317 @@
+use core::fmt;
+
+/// Unit system
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum UnitSystem {
+    /// Metric (SI) units
+    #[default]
+    Metric,
+    /// US customary (imperial) units
+    Imperial,
+}
+
+/// Physical quantity measured by a unit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    /// Temperature
+    Temperature,
+    /// Pressure
+    Pressure,
+    /// Volumetric flow
+    Flow,
+    /// Length, level
+    Length,
+    /// Volume
+    Volume,
+    /// Mass
+    Mass,
+    /// Power
+    Power,
+    /// Dimensionless ratio
+    Ratio,
+}
+
+/// Engineering unit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// Degree Celsius
+    Celsius,
+    /// Degree Fahrenheit
+    Fahrenheit,
+    /// Kelvin
+    Kelvin,
+    /// Bar
+    Bar,
+    /// Kilopascal
+    Kilopascal,
+    /// Pascal
+    Pascal,
+    /// Pound per square inch
+    Psi,
+    /// Cubic meter per hour
+    CubicMeterPerHour,
+    /// Liter per minute
+    LiterPerMinute,
+    /// US gallon per minute
+    GallonPerMinute,
+    /// Millimeter
+    Millimeter,
+    /// Meter
+    Meter,
+    /// Inch
+    Inch,
+    /// Foot
+    Foot,
+    /// Liter
+    Liter,
+    /// Cubic meter
+    CubicMeter,
+    /// US gallon
+    Gallon,
+    /// Kilogram
+    Kilogram,
+    /// Pound
+    Pound,
+    /// Kilowatt
+    Kilowatt,
+    /// Mechanical horsepower
+    Horsepower,
+    /// Percent
+    Percent,
+}
+
+impl Unit {
+    /// Unit symbol
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Celsius => "°C",
+            Unit::Fahrenheit => "°F",
+            Unit::Kelvin => "K",
+            Unit::Bar => "bar",
+            Unit::Kilopascal => "kPa",
+            Unit::Pascal => "Pa",
+            Unit::Psi => "psi",
+            Unit::CubicMeterPerHour => "m³/h",
+            Unit::LiterPerMinute => "l/min",
+            Unit::GallonPerMinute => "gpm",
+            Unit::Millimeter => "mm",
+            Unit::Meter => "m",
+            Unit::Inch => "in",
+            Unit::Foot => "ft",
+            Unit::Liter => "l",
+            Unit::CubicMeter => "m³",
+            Unit::Gallon => "gal",
+            Unit::Kilogram => "kg",
+            Unit::Pound => "lb",
+            Unit::Kilowatt => "kW",
+            Unit::Horsepower => "hp",
+            Unit::Percent => "%",
+        }
+    }
+
+    /// The quantity the unit measures
+    pub fn quantity(self) -> Quantity {
+        match self {
+            Unit::Celsius | Unit::Fahrenheit | Unit::Kelvin => Quantity::Temperature,
+            Unit::Bar | Unit::Kilopascal | Unit::Pascal | Unit::Psi => Quantity::Pressure,
+            Unit::CubicMeterPerHour | Unit::LiterPerMinute | Unit::GallonPerMinute => {
+                Quantity::Flow
+            }
+            Unit::Millimeter | Unit::Meter | Unit::Inch | Unit::Foot => Quantity::Length,
+            Unit::Liter | Unit::CubicMeter | Unit::Gallon => Quantity::Volume,
+            Unit::Kilogram | Unit::Pound => Quantity::Mass,
+            Unit::Kilowatt | Unit::Horsepower => Quantity::Power,
+            Unit::Percent => Quantity::Ratio,
+        }
+    }
+
+    /// The unit system the unit belongs to, `None` for units common for all systems
+    pub fn system(self) -> Option<UnitSystem> {
+        match self {
+            Unit::Fahrenheit
+            | Unit::Psi
+            | Unit::GallonPerMinute
+            | Unit::Inch
+            | Unit::Foot
+            | Unit::Gallon
+            | Unit::Pound
+            | Unit::Horsepower => Some(UnitSystem::Imperial),
+            Unit::Percent => None,
+            _ => Some(UnitSystem::Metric),
+        }
+    }
+
+    /// The matching unit of the same quantity in the target unit system
+    pub fn in_system(self, system: UnitSystem) -> Unit {
+        if self.system().unwrap_or(system) == system {
+            return self;
+        }
+        match (system, self) {
+            (UnitSystem::Metric, Unit::Fahrenheit) => Unit::Celsius,
+            (UnitSystem::Metric, Unit::Psi) => Unit::Bar,
+            (UnitSystem::Metric, Unit::GallonPerMinute) => Unit::CubicMeterPerHour,
+            (UnitSystem::Metric, Unit::Inch) => Unit::Millimeter,
+            (UnitSystem::Metric, Unit::Foot) => Unit::Meter,
+            (UnitSystem::Metric, Unit::Gallon) => Unit::Liter,
+            (UnitSystem::Metric, Unit::Pound) => Unit::Kilogram,
+            (UnitSystem::Metric, Unit::Horsepower) => Unit::Kilowatt,
+            (UnitSystem::Imperial, Unit::Celsius | Unit::Kelvin) => Unit::Fahrenheit,
+            (UnitSystem::Imperial, Unit::Bar | Unit::Kilopascal | Unit::Pascal) => Unit::Psi,
+            (UnitSystem::Imperial, Unit::CubicMeterPerHour | Unit::LiterPerMinute) => {
+                Unit::GallonPerMinute
+            }
+            (UnitSystem::Imperial, Unit::Millimeter) => Unit::Inch,
+            (UnitSystem::Imperial, Unit::Meter) => Unit::Foot,
+            (UnitSystem::Imperial, Unit::Liter | Unit::CubicMeter) => Unit::Gallon,
+            (UnitSystem::Imperial, Unit::Kilogram) => Unit::Pound,
+            (UnitSystem::Imperial, Unit::Kilowatt) => Unit::Horsepower,
+            _ => self,
+        }
+    }
+
+    /// Convert a value to the target unit, returns `None` if the units measure different
+    /// quantities
+    pub fn convert(self, value: f64, target: Unit) -> Option<f64> {
+        if self.quantity() != target.quantity() {
+            return None;
+        }
+        if self == target {
+            return Some(value);
+        }
+        let (scale, offset) = self.to_base();
+        let (target_scale, target_offset) = target.to_base();
+        Some((value * scale + offset - target_offset) / target_scale)
+    }
+
+    /// Scale and offset to convert the value to the base SI unit of the quantity
+    fn to_base(self) -> (f64, f64) {
+        const GALLON: f64 = 3.785_411_784e-3;
+        match self {
+            Unit::Celsius => (1.0, 273.15),
+            Unit::Fahrenheit => (5.0 / 9.0, 459.67 * 5.0 / 9.0),
+            Unit::Kelvin | Unit::Pascal | Unit::Meter | Unit::CubicMeter | Unit::Kilogram => {
+                (1.0, 0.0)
+            }
+            Unit::Bar => (1e5, 0.0),
+            Unit::Kilopascal | Unit::Kilowatt => (1e3, 0.0),
+            Unit::Psi => (6_894.757_293_168, 0.0),
+            Unit::CubicMeterPerHour => (1.0 / 3_600.0, 0.0),
+            Unit::LiterPerMinute => (1e-3 / 60.0, 0.0),
+            Unit::GallonPerMinute => (GALLON / 60.0, 0.0),
+            Unit::Millimeter | Unit::Liter => (1e-3, 0.0),
+            Unit::Inch => (0.025_4, 0.0),
+            Unit::Foot => (0.304_8, 0.0),
+            Unit::Gallon => (GALLON, 0.0),
+            Unit::Pound => (0.453_592_37, 0.0),
+            Unit::Horsepower => (745.699_871_582_27, 0.0),
+            Unit::Percent => (0.01, 0.0),
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Resolved conversion from the unit of the widget value to the displayed unit
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct UnitConversion {
+    from: Unit,
+    to: Unit,
+}
+
+impl UnitConversion {
+    pub(crate) fn new(unit: Option<Unit>, system: Option<UnitSystem>) -> Option<Self> {
+        unit.map(|from| Self {
+            from,
+            to: system.map_or(from, |s| from.in_system(s)),
+        })
+    }
+
+    pub(crate) fn apply(self, value: f64) -> f64 {
+        self.from.convert(value, self.to).unwrap_or(value)
+    }
+
+    pub(crate) fn unit(self) -> Unit {
+        self.to
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Unit, UnitConversion, UnitSystem};
+
+    fn assert_close(value: Option<f64>, expected: f64) {
+        let value = value.expect("the units must be convertible");
+        assert!(
+            (value - expected).abs() <= expected.abs() * 1e-9 + 1e-9,
+            "{value} != {expected}"
+        );
+    }
+
+    #[test]
+    fn test_convert_temperature() {
+        assert_close(Unit::Celsius.convert(100.0, Unit::Fahrenheit), 212.0);
+        assert_close(Unit::Fahrenheit.convert(-40.0, Unit::Celsius), -40.0);
+        assert_close(Unit::Kelvin.convert(0.0, Unit::Celsius), -273.15);
+        assert_close(Unit::Fahrenheit.convert(32.0, Unit::Kelvin), 273.15);
+    }
+
+    #[test]
+    fn test_convert_scale() {
+        assert_close(Unit::Bar.convert(1.0, Unit::Kilopascal), 100.0);
+        assert_close(Unit::Psi.convert(14.503_773_773, Unit::Bar), 1.0);
+        assert_close(
+            Unit::CubicMeterPerHour.convert(1.0, Unit::LiterPerMinute),
+            1e3 / 60.0,
+        );
+        assert_close(Unit::Foot.convert(1.0, Unit::Inch), 12.0);
+        assert_close(Unit::Gallon.convert(1.0, Unit::Liter), 3.785_411_784);
+        assert_close(Unit::Kilogram.convert(1.0, Unit::Pound), 2.204_622_621_8);
+    }
+
+    #[test]
+    fn test_convert_round_trip() {
+        for (from, to) in [
+            (Unit::Celsius, Unit::Fahrenheit),
+            (Unit::Bar, Unit::Psi),
+            (Unit::GallonPerMinute, Unit::CubicMeterPerHour),
+            (Unit::Kilowatt, Unit::Horsepower),
+        ] {
+            let converted = from.convert(37.5, to);
+            assert_close(converted.and_then(|v| to.convert(v, from)), 37.5);
+        }
+    }
+
+    #[test]
+    fn test_convert_different_quantities() {
+        assert_eq!(Unit::Bar.convert(1.0, Unit::Meter), None);
+        assert_eq!(Unit::Percent.convert(1.0, Unit::Celsius), None);
+    }
+
+    #[test]
+    fn test_in_system() {
+        assert_eq!(
+            Unit::Celsius.in_system(UnitSystem::Imperial),
+            Unit::Fahrenheit
+        );
+        assert_eq!(Unit::Kilopascal.in_system(UnitSystem::Imperial), Unit::Psi);
+        assert_eq!(Unit::Psi.in_system(UnitSystem::Metric), Unit::Bar);
+        assert_eq!(Unit::Meter.in_system(UnitSystem::Metric), Unit::Meter);
+        assert_eq!(Unit::Percent.in_system(UnitSystem::Imperial), Unit::Percent);
+    }
+
+    #[test]
+    fn test_unit_conversion() {
+        assert!(UnitConversion::new(None, Some(UnitSystem::Imperial)).is_none());
+        let conversion = UnitConversion::new(Some(Unit::Celsius), Some(UnitSystem::Imperial))
+            .expect("the unit is set");
+        assert_eq!(conversion.unit(), Unit::Fahrenheit);
+        assert_close(Some(conversion.apply(20.0)), 68.0);
+        let conversion = UnitConversion::new(Some(Unit::Celsius), None).expect("the unit is set");
+        assert_eq!(conversion.unit(), Unit::Celsius);
+        assert_close(Some(conversion.apply(20.0)), 20.0);
+    }
+}